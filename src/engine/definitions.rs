@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::species::{Category, Species, SPECIES_COUNT};
+
+// overrides for how a species looks and where the palette lists it. what a
+// species does is part of the engine and cannot be changed from a file
+#[derive(Clone, Default)]
+pub struct Definition {
+    pub color: Option<(u8, u8, u8)>,
+    pub category: Option<Category>,
+    pub description: Option<&'static str>,
+}
+
+// set once at start up, before the first world is drawn
+static DEFINITIONS: OnceLock<Vec<Definition>> = OnceLock::new();
+
+pub fn get(species: Species) -> Option<&'static Definition> {
+    DEFINITIONS.get().map(|definitions| &definitions[species as usize])
+}
+
+fn parse_category(name: &str) -> Option<Category> {
+    Category::all()
+        .into_iter()
+        .find(|category| format!("{:?}", category).eq_ignore_ascii_case(name))
+}
+
+// read a species definition file with a section per species, e.g.
+//
+//     # greener water
+//     [WATR]
+//     color = "#3080a0"
+//     description = "Wet"
+//     category = "liquid"
+//
+// and use it for the rest of the run. species and fields left out keep their defaults
+pub fn load(path: &Path) -> Result<(), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read species definitions {}: {}", path.display(), e))?;
+
+    let mut definitions = vec![Definition::default(); SPECIES_COUNT];
+    let mut current: Option<Species> = None;
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| format!("{}:{}: {}", path.display(), line_no + 1, message);
+
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            let species = Species::from_name(name.trim()).ok_or_else(|| error(format!("unknown species `{}`", name)))?;
+            current = Some(species);
+            continue;
+        }
+
+        let Some(species) = current else {
+            return Err(error("expected a `[SPECIES]` section first".to_string()));
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(error("expected `<field> = \"<value>\"`".to_string()));
+        };
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .ok_or_else(|| error(format!("expected a quoted value for `{}`", key.trim())))?;
+
+        let definition = &mut definitions[species as usize];
+        match key.trim() {
+            "color" => {
+                definition.color = Some(super::import::parse_hex(value).ok_or_else(|| error(format!("bad colour `{}`", value)))?);
+            }
+            "category" => {
+                definition.category = Some(parse_category(value).ok_or_else(|| error(format!("unknown category `{}`", value)))?);
+            }
+            // lives until the program exits, like the built in descriptions
+            "description" => definition.description = Some(Box::leak(value.to_string().into_boxed_str())),
+            key => return Err(error(format!("unknown field `{}`", key))),
+        }
+    }
+
+    DEFINITIONS
+        .set(definitions)
+        .map_err(|_| "species definitions were already loaded".to_string())
+}
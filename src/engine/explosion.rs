@@ -0,0 +1,142 @@
+use rand::Rng;
+
+use super::species::{smoke, Species};
+use super::{Cell, Wind, World, CALM, EMPTY_CELL};
+
+// how far past the blast radius cells are thrown and the pressure wave carries
+const THROW_REACH: i32 = 2;
+// pressure added at the centre of a blast, falling off towards its edge
+const PRESSURE_PULSE: i32 = 120;
+// pressure a gust loses every tick until the air is calm again
+const GUST_SETTLING: u8 = 4;
+// thickness of the smoke a blast leaves
+const BLAST_SMOKE: u8 = 160;
+
+// a blast queued by a cell during a tick, set off once every cell has moved.
+// the per-cell api only reaches two cells out, a blast reaches much further
+#[derive(Clone, Copy, Debug)]
+pub struct Explosion {
+    pub x: i32,
+    pub y: i32,
+    pub radius: i32,
+}
+
+impl World {
+    pub(super) fn detonate(&mut self) {
+        for explosion in std::mem::take(&mut self.explosions) {
+            self.explode(explosion);
+        }
+    }
+
+    // let the pressure left by blasts die down, once calm the wind is reset
+    pub(super) fn settle_winds(&mut self) {
+        let winds = &mut self.winds;
+        self.gusts.retain(|&i| {
+            let pressure = winds[i].pressure.saturating_sub(GUST_SETTLING);
+            if pressure <= CALM.pressure {
+                winds[i] = CALM;
+                return false;
+            }
+            winds[i].pressure = pressure;
+            true
+        });
+    }
+
+    fn explode(&mut self, Explosion { x, y, radius }: Explosion) {
+        // cells inside the blast in order of distance, furthest first, so that
+        // the outer ring has made room by the time the inner cells are pushed
+        let mut inside = Vec::new();
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let distance = dx * dx + dy * dy;
+                if distance <= radius * radius && self.in_bounds(x + dx, y + dy) {
+                    inside.push((distance, dx, dy));
+                }
+            }
+        }
+        inside.sort_by_key(|&(distance, _, _)| std::cmp::Reverse(distance));
+
+        for (distance, dx, dy) in inside {
+            let cell = self.get_cell(x + dx, y + dy);
+            if cell.species == Species::WALL {
+                continue;
+            }
+            // the heart of the blast burns, everything else is thrown clear of it
+            let core = distance * 4 <= radius * radius;
+            if !core && cell.species != Species::EMPT {
+                // with nowhere to land the cell stays put rather than vanish
+                let Some((nx, ny)) = self.landing(x, y, dx, dy, radius) else {
+                    continue;
+                };
+                self.put(nx, ny, cell);
+            }
+            let debris = match self.rng.gen_range(0..4) {
+                0 if core => Cell {
+                    ra: 150 + self.rng.gen_range(0..50),
+                    ..Cell::plain(Species::FIRE)
+                },
+                0 | 1 => smoke(BLAST_SMOKE),
+                _ => EMPTY_CELL,
+            };
+            self.put(x + dx, y + dy, debris);
+        }
+
+        let reach = radius + THROW_REACH;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                if distance > reach as f32 || !self.in_bounds(x + dx, y + dy) {
+                    continue;
+                }
+                let falloff = 1.0 - distance / (reach + 1) as f32;
+                let push = |d: i32| (126 + (d as f32 / distance.max(1.0) * PRESSURE_PULSE as f32 * falloff) as i32).clamp(0, 255) as u8;
+                let i = self.get_index(x + dx, y + dy);
+                let wind = self.winds[i];
+                // air already above calm pressure is settling from an earlier blast
+                if wind.pressure <= CALM.pressure {
+                    self.gusts.push(i);
+                }
+                self.winds[i] = Wind {
+                    dx: push(dx),
+                    dy: push(dy),
+                    pressure: wind.pressure.max(CALM.pressure).saturating_add((PRESSURE_PULSE as f32 * falloff) as u8 + 1),
+                    ..wind
+                };
+            }
+        }
+    }
+
+    // where a cell at (dx, dy) from the blast lands, just past its edge. when
+    // that spot is taken the cell flies on to the first free one behind it,
+    // stopping at walls and the edge of the world
+    fn landing(&mut self, x: i32, y: i32, dx: i32, dy: i32, radius: i32) -> Option<(i32, i32)> {
+        let length = ((dx * dx + dy * dy) as f32).sqrt();
+        let throw = radius + 1 + self.rng.gen_range(0..THROW_REACH + 1);
+        for reach in throw..=radius + 1 + THROW_REACH * 2 {
+            let nx = x + (dx as f32 / length * reach as f32).round() as i32;
+            let ny = y + (dy as f32 / length * reach as f32).round() as i32;
+            if !self.in_bounds(nx, ny) {
+                return None;
+            }
+            match self.get_cell(nx, ny).species {
+                Species::EMPT => return Some((nx, ny)),
+                Species::WALL => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < self.width && y >= 0 && y < self.height
+    }
+
+    fn put(&mut self, x: i32, y: i32, cell: Cell) {
+        let i = self.get_index(x, y);
+        self.cells[i] = Cell {
+            clock: self.generation,
+            ..cell
+        };
+        self.mark_active(x, y);
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use super::species::Species;
+use super::{Cell, World, EMPTY_CELL};
+
+// number of edits kept around for undo
+const MAX_EDITS: usize = 100;
+
+// the cells touched by a single user action, with their value before and after
+#[derive(Default)]
+struct Edit {
+    changes: Vec<(usize, Cell, Cell)>,
+    // position of each touched cell in `changes`, so repeated writes are merged
+    touched: HashMap<usize, usize>,
+}
+
+impl Edit {
+    fn record(&mut self, i: usize, before: Cell, after: Cell) {
+        match self.touched.get(&i) {
+            Some(&pos) => self.changes[pos].2 = after,
+            None => {
+                self.touched.insert(i, self.changes.len());
+                self.changes.push((i, before, after));
+            }
+        }
+    }
+}
+
+// undo and redo stacks of edits made to the world by the user. edits are
+// stored as diffs, undoing one puts back the touched cells and leaves
+// whatever the simulation did to the rest of the world alone
+#[derive(Default)]
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    current: Option<Edit>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    // start recording a new edit, any edit still open is committed first
+    pub fn begin(&mut self) {
+        self.commit();
+        self.current = Some(Edit::default());
+    }
+
+    // finish the open edit and make it undoable
+    pub fn commit(&mut self) {
+        let Some(edit) = self.current.take() else {
+            return;
+        };
+        if edit.changes.is_empty() {
+            return;
+        }
+        self.undo.push(edit);
+        if self.undo.len() > MAX_EDITS {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    // write a cell into the world as part of the open edit
+    pub fn set(&mut self, world: &mut World, x: usize, y: usize, cell: Cell) {
+        let i = x + y * world.width() as usize;
+        let before = world.cells[i];
+        world.edit(i, cell);
+        if let Some(edit) = self.current.as_mut() {
+            edit.record(i, before, cell);
+        }
+    }
+
+    // empty the world as a single undoable edit
+    pub fn clear(&mut self, world: &mut World) {
+        let mut edit = Edit::default();
+        for (i, cell) in world.cells.iter().enumerate() {
+            if cell.species != Species::EMPT {
+                edit.record(i, *cell, EMPTY_CELL);
+            }
+        }
+        world.clear();
+
+        self.commit();
+        self.current = Some(edit);
+        self.commit();
+    }
+
+    pub fn undo(&mut self, world: &mut World) {
+        self.commit();
+        let Some(edit) = self.undo.pop() else {
+            return;
+        };
+        for &(i, before, _) in edit.changes.iter().rev() {
+            world.edit(i, before);
+        }
+        self.redo.push(edit);
+    }
+
+    pub fn redo(&mut self, world: &mut World) {
+        self.commit();
+        let Some(edit) = self.redo.pop() else {
+            return;
+        };
+        for &(i, _, after) in edit.changes.iter() {
+            world.edit(i, after);
+        }
+        self.undo.push(edit);
+    }
+}
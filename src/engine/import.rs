@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::Path;
+
+use super::species::Species;
+use super::{Cell, World, MAX_CELLS};
+
+// pixels more transparent than this are treated as empty space
+const ALPHA_CUTOFF: u8 = 128;
+
+// a colour -> species table used to translate image pixels into cells
+pub type Palette = Vec<((u8, u8, u8), Species)>;
+
+// the default palette is the base colour of every species
+pub fn default_palette() -> Palette {
+    Species::all()
+        .into_iter()
+        .map(|species| (species.color(), species))
+        .collect()
+}
+
+// parse a palette file where every line is `<hex colour> <species>`, e.g.
+//
+//     // water and walls
+//     #0000ff WATR
+//     ffffff  WALL
+//
+// blank lines and lines starting with `//` are ignored
+pub fn load_palette(path: &Path) -> Result<Palette, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read palette {}: {}", path.display(), e))?;
+
+    let mut palette = Palette::new();
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let (Some(hex), Some(name), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!(
+                "{}:{}: expected `<hex colour> <species>`",
+                path.display(),
+                line_no + 1
+            ));
+        };
+        let color = parse_hex(hex)
+            .ok_or_else(|| format!("{}:{}: bad colour `{}`", path.display(), line_no + 1, hex))?;
+        let species = Species::from_name(name).ok_or_else(|| {
+            format!("{}:{}: unknown species `{}`", path.display(), line_no + 1, name)
+        })?;
+        palette.push((color, species));
+    }
+
+    if palette.is_empty() {
+        return Err(format!("palette {} has no entries", path.display()));
+    }
+    Ok(palette)
+}
+
+pub(super) fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    Some(((value >> 16) as u8, (value >> 8) as u8, value as u8))
+}
+
+fn nearest_species(palette: &Palette, (r, g, b): (u8, u8, u8)) -> Species {
+    // first entry wins on ties, so WALL beats GAS and GOL for pure white
+    palette
+        .iter()
+        .min_by_key(|((pr, pg, pb), _)| {
+            let dr = r as i32 - *pr as i32;
+            let dg = g as i32 - *pg as i32;
+            let db = b as i32 - *pb as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(_, species)| *species)
+        .unwrap_or(Species::EMPT)
+}
+
+impl World {
+    // build a world the size of the image, with each pixel becoming the
+    // species whose colour is closest to it
+    pub fn from_image(path: &Path, palette: Option<&Path>) -> Result<World, String> {
+        let palette = match palette {
+            Some(palette) => load_palette(palette)?,
+            None => default_palette(),
+        };
+
+        let image = image::open(path)
+            .map_err(|e| format!("failed to open image {}: {}", path.display(), e))?
+            .to_rgba8();
+
+        let (width, height) = (image.width(), image.height());
+        if width as u64 * height as u64 > MAX_CELLS as u64 {
+            return Err(format!(
+                "image {} is {}x{}, a world can have at most {} cells",
+                path.display(),
+                width,
+                height,
+                MAX_CELLS
+            ));
+        }
+        let mut world = World::new(width as i32, height as i32);
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let [r, g, b, a] = pixel.0;
+            let species = if a < ALPHA_CUTOFF {
+                Species::EMPT
+            } else {
+                nearest_species(&palette, (r, g, b))
+            };
+            if species != Species::EMPT {
+                world.set(x as usize, y as usize, Cell::new(species));
+            }
+        }
+        Ok(world)
+    }
+
+    // build a world from a text file where every character is a cell, see
+    // `Species::symbol`. rows shorter than the longest one are padded with empty space
+    pub fn from_ascii(path: &Path) -> Result<World, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read map {}: {}", path.display(), e))?;
+        let rows: Vec<&str> = text.lines().collect();
+        let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+        if width == 0 {
+            return Err(format!("map {} is empty", path.display()));
+        }
+
+        let mut world = World::new(width as i32, rows.len() as i32);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, symbol) in row.chars().enumerate() {
+                let species = Species::from_symbol(symbol).ok_or_else(|| {
+                    format!("{}:{}:{}: unknown symbol `{}`", path.display(), y + 1, x + 1, symbol)
+                })?;
+                if species != Species::EMPT {
+                    world.set(x, y, Cell::new(species));
+                }
+            }
+        }
+        Ok(world)
+    }
+
+    // load a saved world, an ascii map or an image depending on the extension
+    pub fn open(path: &Path, palette: Option<&Path>) -> Result<World, String> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("crumb") => World::load(path),
+            Some("txt") => World::from_ascii(path),
+            _ => World::from_image(path, palette),
+        }
+    }
+}
@@ -3,13 +3,22 @@ use std::usize;
 use crate::interface::defaults::{UI_X, UI_Y};
 
 use super::interface::defaults;
+pub mod definitions;
+pub mod explosion;
+pub mod history;
+pub mod import;
+pub mod save;
 pub mod species;
+pub mod timeline;
 use rand::{Rng, SeedableRng};
-use species::Species;
+use explosion::Explosion;
+use history::History;
+use species::{Species, SPECIES_COUNT};
+use timeline::Timeline;
 
 use rand_xoshiro::SplitMix64;
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Cell {
     pub species: Species,
     pub ra: u8,
@@ -30,6 +39,17 @@ impl Cell {
         }
     }
 
+    // a cell with no state, for species created by the simulation itself.
+    // `new` draws on the thread rng, which would make replays differ
+    pub const fn plain(species: Species) -> Cell {
+        Cell {
+            species,
+            ra: 0,
+            rb: 0,
+            clock: 0,
+        }
+    }
+
     pub fn get_species(&self) -> Species {
         self.species
     }
@@ -39,6 +59,16 @@ impl Cell {
     }
 }
 
+// the most cells a world may have, well within what the cell indices can address
+pub const MAX_CELLS: i32 = 1 << 24;
+
+// side length of the square regions activity is tracked in
+const CHUNK_SIZE: i32 = 16;
+
+fn chunks(length: i32) -> i32 {
+    (length + CHUNK_SIZE - 1) / CHUNK_SIZE
+}
+
 static EMPTY_CELL: Cell = Cell {
     species: Species::EMPT,
     ra: 0,
@@ -47,25 +77,54 @@ static EMPTY_CELL: Cell = Cell {
 };
 pub struct Engine {
     pub world: World,
+    pub history: History,
+    pub timeline: Timeline,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Wind {
-    dx: u8,
-    dy: u8,
-    pressure: u8,
-    density: u8,
+    pub dx: u8,
+    pub dy: u8,
+    pub pressure: u8,
+    pub density: u8,
 }
 
+// the air everywhere before anything disturbs it
+pub const CALM: Wind = Wind {
+    dx: 0,
+    dy: 0,
+    pressure: 100,
+    density: 0,
+};
+
 impl Engine {
     pub fn new() -> Self {
+        // the canvas is drawn at 2x scale, the rest of the window belongs to the UI
+        Engine::with_world(World::new(
+            (defaults::WIDTH / 2 - UI_X) as i32,
+            (defaults::HEIGHT / 2 - UI_Y) as i32,
+        ))
+    }
+
+    pub fn with_world(world: World) -> Self {
         Engine {
-            world: World::new(
-                defaults::WIDTH as i32 - UI_X as i32,
-                defaults::HEIGHT as i32 - UI_Y as i32,
-            ),
+            world,
+            history: History::new(),
+            timeline: Timeline::new(),
         }
     }
+
+    // going back in time leaves the undo history describing cells that have
+    // since changed, so it is dropped
+    pub fn rewind(&mut self) {
+        self.timeline.rewind(&mut self.world);
+        self.history = History::new();
+    }
+
+    pub fn step_back(&mut self) {
+        self.timeline.step_back(&mut self.world);
+        self.history = History::new();
+    }
 }
 
 #[allow(dead_code)]
@@ -77,6 +136,14 @@ pub struct World {
     pub generation: u8,
     burns: Vec<Wind>,
     pub rng: SplitMix64,
+    // cells changed from outside the simulation since the last tick
+    edits: Vec<(usize, Cell)>,
+    // chunks in which a cell changed during the last tick
+    active_chunks: Vec<bool>,
+    // blasts set off during this tick
+    explosions: Vec<Explosion>,
+    // winds stirred up by blasts that have yet to settle
+    gusts: Vec<usize>,
 }
 
 pub struct Api<'a> {
@@ -166,13 +233,26 @@ impl<'a> Api<'a> {
             return;
         }
         let i = self.world.get_index(nx, ny);
+        let old = self.world.cells[i];
+        if old.species != v.species || old.ra != v.ra || old.rb != v.rb {
+            self.world.mark_active(nx, ny);
+        }
         // v.clock += 1;
         self.world.cells[i] = v;
         self.world.cells[i].clock = self.world.generation.wrapping_add(1);
     }
 
+    // blow up everything within `radius` of this cell once the tick is over
+    pub fn explode(&mut self, radius: i32) {
+        self.world.explosions.push(Explosion {
+            x: self.x,
+            y: self.y,
+            radius,
+        });
+    }
+
     pub fn rand_dir_2(&mut self) -> i32 {
-        let i = rand::thread_rng().gen_range(0..100);
+        let i = self.rand_int(100);
         if (i % 2) == 0 {
             -1
         } else {
@@ -196,14 +276,37 @@ impl World {
             Species::EMPT => 500,
             Species::WALL => 500,
             Species::GOL => 500,
+            Species::STNE => 500,
+            Species::ICE => 500,
+            Species::C4 => 500,
+            Species::PLANT => 500,
+            Species::FLOWER => 500,
+            Species::METAL => 500,
+            Species::SPARK => 500,
+            Species::BATTERY => 500,
+            Species::CLONE => 500,
+            Species::VOID => 500,
+            Species::CHARCOAL => 500,
 
             Species::OIL => 50,
             Species::WATR => 50,
+            Species::LAVA => 80,
+            Species::ACID => 50,
+            Species::SALTWATER => 50,
 
             Species::SAND => 30,
+            Species::SALT => 30,
+            Species::WETSAND => 100,
+            Species::MUD => 100,
             Species::DUST => 10,
+            Species::GUNPOWDER => 20,
+            Species::SEED => 10,
+            Species::SNOW => 8,
+            Species::ASH => 4,
+            Species::SOOT => 3,
             Species::FIRE => 5,
             Species::GAS => 5,
+            Species::STEAM => 5,
 
             _ => 40,
         };
@@ -251,19 +354,12 @@ impl World {
 impl World {
     pub fn new(width: i32, height: i32) -> World {
         let rng: SplitMix64 = SeedableRng::seed_from_u64(0x734f6b89de5f83cc);
+        let size = (width * height) as usize;
         World {
-            width: (width - UI_X as i32) / 2 as i32,
-            height: (height - UI_Y as i32) as i32,
-            cells: vec![Cell::new(Species::EMPT); defaults::WIDTH * defaults::HEIGHT],
-            winds: vec![
-                Wind {
-                    dx: 0,
-                    dy: 0,
-                    pressure: 100,
-                    density: 0
-                };
-                defaults::WIDTH * defaults::HEIGHT
-            ],
+            width,
+            height,
+            cells: vec![Cell::new(Species::EMPT); size],
+            winds: vec![CALM; size],
             generation: 0,
             burns: vec![
                 Wind {
@@ -272,11 +368,29 @@ impl World {
                     pressure: 0,
                     density: 0
                 };
-                defaults::WIDTH * defaults::HEIGHT
+                size
             ],
             rng,
+            edits: Vec::new(),
+            active_chunks: vec![false; (chunks(width) * chunks(height)) as usize],
+            explosions: Vec::new(),
+            gusts: Vec::new(),
         }
     }
+    // restart the random numbers the simulation draws from, the same seed and
+    // edits always play out the same way
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = SeedableRng::seed_from_u64(seed);
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
     pub fn get_index(&self, x: i32, y: i32) -> usize {
         (x + y * self.width) as usize
     }
@@ -299,11 +413,44 @@ impl World {
 
     pub fn clear(&mut self) {
         for i in 0..self.cells.len() {
-            self.cells[i] = EMPTY_CELL;
+            if self.cells[i].species != Species::EMPT {
+                self.edit(i, EMPTY_CELL);
+            }
+        }
+    }
+
+    // change a cell from outside the simulation, e.g. with the brush. edits are
+    // logged so that they can be replayed on top of an earlier snapshot
+    pub fn edit(&mut self, i: usize, cell: Cell) {
+        self.cells[i] = cell;
+        self.edits.push((i, cell));
+    }
+
+    pub fn take_edits(&mut self) -> Vec<(usize, Cell)> {
+        std::mem::take(&mut self.edits)
+    }
+
+    fn mark_active(&mut self, x: i32, y: i32) {
+        let i = x / CHUNK_SIZE + (y / CHUNK_SIZE) * chunks(self.width);
+        self.active_chunks[i as usize] = true;
+    }
+
+    // chunks that changed during the last tick, and the number of chunks in total
+    pub fn chunk_activity(&self) -> (usize, usize) {
+        let active = self.active_chunks.iter().filter(|active| **active).count();
+        (active, self.active_chunks.len())
+    }
+
+    // number of cells of every species, indexed by the species value
+    pub fn species_counts(&self) -> [usize; SPECIES_COUNT] {
+        let mut counts = [0; SPECIES_COUNT];
+        for cell in self.cells.iter() {
+            counts[cell.species as usize] += 1;
         }
+        counts
     }
 
-    fn get_wind(&self, x: i32, y: i32) -> Wind {
+    pub fn get_wind(&self, x: i32, y: i32) -> Wind {
         let i = self.get_index(x, y);
         return self.winds[i];
     }
@@ -328,6 +475,7 @@ impl World {
         }
         */
         self.generation = (self.generation + 1) % 255;
+        self.active_chunks.fill(false);
 
         for x in 0..self.width - 1 {
             let scanx = if self.generation % 2 == 0 {
@@ -358,6 +506,8 @@ impl World {
             }
             //std::thread::sleep(std::time::Duration::from_millis(1/600));
         }
+        self.detonate();
+        self.settle_winds();
 
         self.generation = (self.generation + 1) % 255;
     }
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::Path;
+
+use super::species::Species;
+use super::{Cell, World, MAX_CELLS};
+
+// saved worlds start with these bytes followed by the format version
+const MAGIC: &[u8; 4] = b"CRMB";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 8;
+const CELL_LEN: usize = 4;
+
+// a saved world is the header, the width and height as little endian u32s and
+// then every cell row by row as its species, ra, rb and clock bytes. unlike an
+// image this keeps the full state of every cell
+impl World {
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.cells.len() * CELL_LEN);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(self.width() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.height() as u32).to_le_bytes());
+        for cell in self.cells.iter() {
+            bytes.extend_from_slice(&[cell.species as u8, cell.ra, cell.rb, cell.clock]);
+        }
+        fs::write(path, bytes).map_err(|e| format!("failed to save world {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<World, String> {
+        let bytes = fs::read(path).map_err(|e| format!("failed to read world {}: {}", path.display(), e))?;
+        if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(format!("{} is not a saved world", path.display()));
+        }
+        if bytes[MAGIC.len()] != VERSION {
+            return Err(format!("{} has unsupported version {}", path.display(), bytes[MAGIC.len()]));
+        }
+
+        let read_u32 = |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]) as usize;
+        let width = read_u32(MAGIC.len() + 1);
+        let height = read_u32(MAGIC.len() + 5);
+        let cells = &bytes[HEADER_LEN..];
+        if width == 0 || height == 0 || width.checked_mul(height).and_then(|n| n.checked_mul(CELL_LEN)) != Some(cells.len()) {
+            return Err(format!("{} has the wrong size for a {}x{} world", path.display(), width, height));
+        }
+        if width * height > MAX_CELLS as usize {
+            return Err(format!("{} is {}x{}, a world can have at most {} cells", path.display(), width, height, MAX_CELLS));
+        }
+
+        let all = Species::all();
+        let mut world = World::new(width as i32, height as i32);
+        for (i, chunk) in cells.chunks_exact(CELL_LEN).enumerate() {
+            let species = *all
+                .get(chunk[0] as usize)
+                .ok_or_else(|| format!("{}: unknown species {} at cell {}", path.display(), chunk[0], i))?;
+            world.cells[i] = Cell {
+                species,
+                ra: chunk[1],
+                rb: chunk[2],
+                clock: chunk[3],
+            };
+        }
+        Ok(world)
+    }
+}
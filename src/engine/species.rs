@@ -1,4 +1,4 @@
-use super::{Api, Wind};
+use super::{definitions, Api, Wind};
 use crate::engine::{Cell, EMPTY_CELL};
 
 #[derive(Clone, Copy, PartialEq, Debug, Eq)]
@@ -17,10 +17,139 @@ pub enum Species {
     GOL = 9,
     WOOD = 10,
     OXGN = 11,
-    HYGN,
+    HYGN = 12,
+    LAVA = 13,
+    STNE = 14,
+    STEAM = 15,
+    ICE = 16,
+    SNOW = 17,
+    ACID = 18,
+    GUNPOWDER = 19,
+    C4 = 20,
+    SEED = 21,
+    PLANT = 22,
+    FLOWER = 23,
+    METAL = 24,
+    SPARK = 25,
+    BATTERY = 26,
+    CLONE = 27,
+    VOID = 28,
+    CHARCOAL = 29,
+    ASH = 30,
+    SOOT = 31,
+    SALT = 32,
+    SALTWATER = 33,
+    WETSAND = 34,
+    MUD = 35,
+}
+
+pub const SPECIES_COUNT: usize = 36;
+
+// out of how many ticks lava gets to flow sideways, water flows every tick
+const LAVA_VISCOSITY: i32 = 6;
+// ticks, on average, before lava cools into stone on its own
+const LAVA_COOLING: i32 = 3000;
+// ticks, on average, before steam condenses back into water
+const STEAM_CONDENSING: i32 = 400;
+// the same for steam stuck under a ceiling, which cools faster
+const STEAM_CEILING: i32 = 40;
+// ticks, on average, before ice or snow in water melts
+const ICE_MELTING: i32 = 600;
+const SNOW_MELTING: i32 = 150;
+// ticks, on average, before snow with snow on top of it packs into ice
+const SNOW_COMPACTING: i32 = 800;
+// ticks, on average, before acid touching water is diluted into it
+const ACID_DILUTING: i32 = 30;
+// radius in cells of the blast a grain of gunpowder and a block of c4 go off with
+const GUNPOWDER_BLAST: i32 = 3;
+const C4_BLAST: i32 = 9;
+// ticks, on average, before a seed on wet sand sprouts
+const SEED_GERMINATING: i32 = 60;
+// energy a sprout starts out with, plus up to as much again. every cell a plant
+// grows uses one up
+const PLANT_ENERGY: i32 = 20;
+// ticks, on average, between two cells of growth and between two sips of water
+const PLANT_GROWING: i32 = 12;
+const PLANT_DRINKING: i32 = 40;
+// energy a sip of water is worth, and the most a plant cell can store
+const PLANT_WATER_ENERGY: u8 = 10;
+const PLANT_MAX_ENERGY: u8 = 60;
+// out of how many cells of growth a tip changes direction or splits in two
+const PLANT_TURNING: i32 = 5;
+const PLANT_BRANCHING: i32 = 9;
+// out of how many tips that run out of energy end in a flower
+const PLANT_FLOWERING: i32 = 2;
+// directions a plant grows in, kept in the rb of a growing tip
+const PLANT_DIRECTIONS: [(i32, i32); 3] = [(0, -1), (-1, -1), (1, -1)];
+// ticks a spark spends as the tail behind its head before the conductor
+// underneath can carry current again
+const SPARK_TAIL: u8 = 2;
+// out of how many ticks a spark sets a flammable neighbour alight, and splits
+// neighbouring water into hydrogen and oxygen
+const SPARK_IGNITING: i32 = 6;
+const SPARK_ELECTROLYSING: i32 = 10;
+// ticks between two pulses of a battery
+const BATTERY_PERIOD: u8 = 12;
+// ticks burning wood smoulders as charcoal, counted down every other tick,
+// before it is left as ash
+const CHARCOAL_BURNING: u8 = 250;
+// out of how many ticks glowing charcoal puffs out smoke and a flame
+const CHARCOAL_SMOKING: i32 = 15;
+const CHARCOAL_FLAMING: i32 = 4;
+// out of how many drops of burnt out oil leave soot behind
+const OIL_SOOTING: i32 = 3;
+// ticks, on average, stuck soot takes to stain a cell next to it, and the rb
+// a stained cell is marked with
+const SOOT_STAINING: i32 = 20;
+const SOOT_STAIN: u8 = 200;
+// how thick the smoke off each fuel is, thick smoke is darker and lingers
+const FIRE_SMOKE: u8 = 40;
+const LAVA_SMOKE: u8 = 80;
+const CHARCOAL_SMOKE: u8 = 120;
+const OIL_SMOKE: u8 = 220;
+// thickness of smoke that has none set, like smoke from worlds saved before
+// smoke had a thickness. it lasts about as long as that smoke used to
+pub const SMOKE_DENSITY: u8 = 100;
+// ticks, on average, salt takes to dissolve in water or eat into ice and snow
+const SALT_DISSOLVING: i32 = 8;
+const SALT_THAWING: i32 = 30;
+// how many times faster saltwater melts ice and snow than fresh water, salt
+// lowers the freezing point
+const SALTWATER_THAWING: i32 = 10;
+// out of how many ticks saltwater swaps places with fresh water below it
+const SALTWATER_SINKING: i32 = 3;
+// ticks, on average, dry sand and dust next to water take to soak it up
+const POWDER_SOAKING: i32 = 20;
+// ticks next to heat a wet powder takes to dry out
+const POWDER_DRYING: u8 = 40;
+// how much wet powders clump together, see `slides`
+const WETSAND_COHESION: i32 = 2;
+const MUD_COHESION: i32 = 3;
+
+// groups the palette sorts species into
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Category {
+    Powder,
+    Liquid,
+    Gas,
+    Solid,
+    Life,
+    Special,
+}
+
+impl Category {
+    pub fn all() -> [Category; 6] {
+        [
+            Category::Powder,
+            Category::Liquid,
+            Category::Gas,
+            Category::Solid,
+            Category::Life,
+            Category::Special,
+        ]
+    }
 }
 
-pub const SPECIES_COUNT: usize = 13;
 // create an impl for species which returns an array of species in the order they are defined
 impl Species {
     pub fn all() -> [Species; SPECIES_COUNT] {
@@ -38,16 +167,229 @@ impl Species {
             Species::WOOD,
             Species::OXGN,
             Species::HYGN,
+            Species::LAVA,
+            Species::STNE,
+            Species::STEAM,
+            Species::ICE,
+            Species::SNOW,
+            Species::ACID,
+            Species::GUNPOWDER,
+            Species::C4,
+            Species::SEED,
+            Species::PLANT,
+            Species::FLOWER,
+            Species::METAL,
+            Species::SPARK,
+            Species::BATTERY,
+            Species::CLONE,
+            Species::VOID,
+            Species::CHARCOAL,
+            Species::ASH,
+            Species::SOOT,
+            Species::SALT,
+            Species::SALTWATER,
+            Species::WETSAND,
+            Species::MUD,
         ]
     }
+
+    pub fn from_name(name: &str) -> Option<Species> {
+        Species::all()
+            .into_iter()
+            .find(|species| format!("{:?}", species).eq_ignore_ascii_case(name))
+    }
+
+    // character used for the species in ascii maps
+    pub fn symbol(&self) -> char {
+        match self {
+            Species::EMPT => ' ',
+            Species::WALL => '#',
+            Species::DUST => ':',
+            Species::SAND => 's',
+            Species::WATR => '~',
+            Species::GAS => 'g',
+            Species::OIL => 'o',
+            Species::FIRE => '^',
+            Species::SMKE => '%',
+            Species::GOL => '@',
+            Species::WOOD => 'w',
+            Species::OXGN => 'O',
+            Species::HYGN => 'H',
+            Species::LAVA => 'L',
+            Species::STNE => 'r',
+            Species::STEAM => 'v',
+            Species::ICE => 'i',
+            Species::SNOW => '*',
+            Species::ACID => 'a',
+            Species::GUNPOWDER => 'p',
+            Species::C4 => 'c',
+            Species::SEED => 'e',
+            Species::PLANT => 'P',
+            Species::FLOWER => 'f',
+            Species::METAL => 'm',
+            Species::SPARK => '!',
+            Species::BATTERY => 'B',
+            Species::CLONE => 'C',
+            Species::VOID => 'V',
+            Species::CHARCOAL => 'k',
+            Species::ASH => 'A',
+            Species::SOOT => 'S',
+            Species::SALT => 'n',
+            Species::SALTWATER => '=',
+            Species::WETSAND => 'W',
+            Species::MUD => 'M',
+        }
+    }
+
+    pub fn from_symbol(symbol: char) -> Option<Species> {
+        // dots are easier to see than spaces when writing maps by hand
+        if symbol == '.' {
+            return Some(Species::EMPT);
+        }
+        Species::all().into_iter().find(|species| species.symbol() == symbol)
+    }
+
+    pub fn category(&self) -> Category {
+        if let Some(category) = definitions::get(*self).and_then(|definition| definition.category) {
+            return category;
+        }
+        match self {
+            Species::EMPT => Category::Special,
+            Species::WALL => Category::Solid,
+            Species::DUST => Category::Powder,
+            Species::SAND => Category::Powder,
+            Species::WATR => Category::Liquid,
+            Species::GAS => Category::Gas,
+            Species::OIL => Category::Liquid,
+            Species::FIRE => Category::Special,
+            Species::SMKE => Category::Gas,
+            Species::GOL => Category::Life,
+            Species::WOOD => Category::Solid,
+            Species::OXGN => Category::Gas,
+            Species::HYGN => Category::Gas,
+            Species::LAVA => Category::Liquid,
+            Species::STNE => Category::Solid,
+            Species::STEAM => Category::Gas,
+            Species::ICE => Category::Solid,
+            Species::SNOW => Category::Powder,
+            Species::ACID => Category::Liquid,
+            Species::GUNPOWDER => Category::Powder,
+            Species::C4 => Category::Solid,
+            Species::SEED => Category::Powder,
+            Species::PLANT => Category::Life,
+            Species::FLOWER => Category::Life,
+            Species::METAL => Category::Solid,
+            Species::SPARK => Category::Special,
+            Species::BATTERY => Category::Special,
+            Species::CLONE => Category::Special,
+            Species::VOID => Category::Special,
+            Species::CHARCOAL => Category::Solid,
+            Species::ASH => Category::Powder,
+            Species::SOOT => Category::Powder,
+            Species::SALT => Category::Powder,
+            Species::SALTWATER => Category::Liquid,
+            Species::WETSAND => Category::Powder,
+            Species::MUD => Category::Powder,
+        }
+    }
+
+    // one line summary of how the species behaves
+    pub fn description(&self) -> &'static str {
+        if let Some(description) = definitions::get(*self).and_then(|definition| definition.description) {
+            return description;
+        }
+        match self {
+            Species::EMPT => "Empty space, paints as an eraser",
+            Species::WALL => "Indestructible, never moves",
+            Species::DUST => "Light powder, catches fire easily",
+            Species::SAND => "Piles up and sinks through liquids",
+            Species::WATR => "Flows and levels out, puts out fire",
+            Species::GAS => "Still and flammable",
+            Species::OIL => "Flows, floats on water and burns for a long time",
+            Species::FIRE => "Burns out quickly, spreads to dust and gas",
+            Species::SMKE => "Rises and fades away",
+            Species::GOL => "Conway's Game of Life",
+            Species::WOOD => "Solid block",
+            Species::OXGN => "Drifts around, forms water with hydrogen",
+            Species::HYGN => "Drifts around, forms water with oxygen",
+            Species::LAVA => "Thick and hot, sets things alight and cools into stone",
+            Species::STNE => "Solid rock left behind by cooled lava",
+            Species::STEAM => "Rises and condenses back into water when it cools",
+            Species::ICE => "Frozen water, melts near fire or lava",
+            Species::SNOW => "Drifts down, packs into ice and melts into water",
+            Species::ACID => "Eats through anything but walls, water weakens it",
+            Species::GUNPOWDER => "Pours like dust and blows up at the first spark",
+            Species::C4 => "Stays put until heat sets off a large blast",
+            Species::SEED => "Sprouts when it lands on sand or mud next to water",
+            Species::PLANT => "Grows up and branches out while it has water, burns",
+            Species::FLOWER => "Blooms where a plant stops growing",
+            Species::METAL => "Carries sparks along wires",
+            Species::SPARK => "Current running through a conductor, heats and splits water",
+            Species::BATTERY => "Sends a spark into touching metal every few ticks",
+            Species::CLONE => "Copies the first thing that touches it, forever",
+            Species::VOID => "Swallows anything that touches it",
+            Species::CHARCOAL => "Left by burning wood, smoulders hot and slow into ash",
+            Species::ASH => "Light powder that settles slowly after a fire",
+            Species::SOOT => "Left by burning oil, sticks to surfaces and stains them black",
+            Species::SALT => "Dissolves in water and melts ice",
+            Species::SALTWATER => "Sinks under fresh water, conducts and boils off into salt",
+            Species::WETSAND => "Sand that soaked up water, clumps into steep piles",
+            Species::MUD => "Dust that soaked up water, thick and slow to slide",
+        }
+    }
+
+    // base rgb colour of the species before any per-cell variation
+    pub fn color(&self) -> (u8, u8, u8) {
+        if let Some(color) = definitions::get(*self).and_then(|definition| definition.color) {
+            return color;
+        }
+        match self {
+            Species::EMPT => (0, 0, 0),
+            Species::WALL => (255, 255, 255),
+            Species::DUST => (255, 200, 230),
+            Species::SAND => (255, 200, 100),
+            Species::WATR => (100, 100, 255),
+            Species::GAS => (255, 255, 255),
+            Species::OIL => (255, 100, 0),
+            Species::FIRE => (255, 120, 0),
+            Species::SMKE => (100, 100, 100),
+            Species::GOL => (255, 255, 255),
+            Species::WOOD => (100, 50, 0),
+            Species::OXGN => (146, 182, 213),
+            Species::HYGN => (51, 71, 109),
+            Species::LAVA => (245, 70, 20),
+            Species::STNE => (120, 120, 130),
+            Species::STEAM => (210, 210, 235),
+            Species::ICE => (170, 220, 255),
+            Species::SNOW => (240, 240, 255),
+            Species::ACID => (140, 255, 40),
+            Species::GUNPOWDER => (70, 70, 80),
+            Species::C4 => (230, 225, 190),
+            Species::SEED => (170, 130, 70),
+            Species::PLANT => (50, 170, 60),
+            Species::FLOWER => (250, 120, 200),
+            Species::METAL => (110, 130, 160),
+            Species::SPARK => (255, 250, 130),
+            Species::BATTERY => (200, 60, 60),
+            Species::CLONE => (200, 200, 60),
+            Species::VOID => (60, 20, 70),
+            Species::CHARCOAL => (60, 52, 50),
+            Species::ASH => (190, 190, 185),
+            Species::SOOT => (55, 55, 60),
+            Species::SALT => (235, 230, 225),
+            Species::SALTWATER => (70, 110, 220),
+            Species::WETSAND => (190, 150, 80),
+            Species::MUD => (110, 80, 55),
+        }
+    }
 }
 impl Species {
     pub fn update(&self, cell: Cell, api: Api) {
         match self {
             Species::EMPT => {}
             Species::WALL => {}
-            Species::DUST => update_dust(cell, api),
-            Species::SAND => update_sand(cell, api),
+            Species::DUST => update_dry_dust(cell, api),
+            Species::SAND => update_dry_sand(cell, api),
             Species::WATR => update_water(cell, api),
             Species::GAS => {},
             Species::OIL => update_oil(cell, api),
@@ -57,38 +399,71 @@ impl Species {
             Species::WOOD => update_wood(cell, api),
             Species::OXGN => update_oxygen(cell, api),
             Species::HYGN => update_hydrogen(cell, api),
+            Species::LAVA => update_lava(cell, api),
+            Species::STNE => {}
+            Species::STEAM => update_steam(cell, api),
+            Species::ICE => update_ice(cell, api),
+            Species::SNOW => update_snow(cell, api),
+            Species::ACID => update_acid(cell, api),
+            Species::GUNPOWDER => update_gunpowder(cell, api),
+            Species::C4 => update_c4(cell, api),
+            Species::SEED => update_seed(cell, api),
+            Species::PLANT => update_plant(cell, api),
+            Species::FLOWER => {}
+            Species::METAL => {}
+            Species::SPARK => update_spark(cell, api),
+            Species::BATTERY => update_battery(cell, api),
+            Species::CLONE => update_clone(cell, api),
+            Species::VOID => update_void(cell, api),
+            Species::CHARCOAL => update_charcoal(cell, api),
+            Species::ASH => update_ash(cell, api),
+            Species::SOOT => update_soot(cell, api),
+            Species::SALT => update_salt(cell, api),
+            Species::SALTWATER => update_saltwater(cell, api),
+            Species::WETSAND => update_wetsand(cell, api),
+            Species::MUD => update_mud(cell, api),
         }
     }
 }
 
-pub fn update_dust(cell: Cell, mut api: Api) {
-    let dx = api.rand_dir();
-    let nb = api.get(dx, 1);
-    let nbr = api.get(dx + 1, 1);
-    let nbl = api.get(dx - 1, 1);
+// whether a grain with the given cohesion that is resting on something slides
+// sideways down to (dx, 1). the drop there has to be `cohesion` cells deep, as
+// far as the api reaches, and the grain only goes once in `cohesion` ticks.
+// loose powders have a cohesion of 0 and always slide
+fn slides(api: &mut Api, dx: i32, cohesion: i32) -> bool {
+    if cohesion <= 1 {
+        return true;
+    }
+    (1..=cohesion.min(2)).all(|dy| api.get(dx, dy).species == Species::EMPT) && api.once_in(cohesion)
+}
 
-    if nb.species == Species::EMPT {
-        api.set(dx, 1, cell);
-        api.set(0, 0, EMPTY_CELL);
-    } else if nbr.species == Species::EMPT {
-        api.set(dx + 1, 1, cell);
-        api.set(0, 0, EMPTY_CELL);
-    } else if nbl.species == Species::EMPT {
-        api.set(dx - 1, 1, cell);
-        api.set(0, 0, EMPTY_CELL);
+pub fn update_dust(cell: Cell, mut api: Api, cohesion: i32) {
+    let dx = api.rand_dir();
+    let resting = api.get(0, 1).species != Species::EMPT;
+    let Some(x) = [dx, dx + 1, dx - 1]
+        .into_iter()
+        .find(|x| api.get(*x, 1).species == Species::EMPT)
+    else {
+        return;
+    };
+    if x != 0 && resting && !slides(&mut api, x, cohesion) {
+        return;
     }
+    api.set(x, 1, cell);
+    api.set(0, 0, EMPTY_CELL);
 }
-pub fn update_sand(cell: Cell, mut api: Api) {
+pub fn update_sand(cell: Cell, mut api: Api, cohesion: i32) {
     let dx = api.rand_dir_2();
 
     let nbr = api.get(0, 1);
     if nbr.species == Species::EMPT {
         api.set(0, 0, EMPTY_CELL);
         api.set(0, 1, cell);
-    } else if api.get(dx, 1).species == Species::EMPT {
+    } else if api.get(dx, 1).species == Species::EMPT && slides(&mut api, dx, cohesion) {
         api.set(0, 0, EMPTY_CELL);
         api.set(dx, 1, cell);
     } else if nbr.species == Species::WATR
+        || nbr.species == Species::SALTWATER
         || nbr.species == Species::GAS
         || nbr.species == Species::OIL
     {
@@ -99,7 +474,66 @@ pub fn update_sand(cell: Cell, mut api: Api) {
     }
 }
 
-pub fn update_water(cell: Cell, mut api: Api) {
+// dry sand and dust soak up the water next to them, returns whether they did
+fn soaks(api: &mut Api, wet: Species) -> bool {
+    let (dx, dy) = api.rand_vec_8();
+    if matches!(api.get(dx, dy).species, Species::WATR | Species::SALTWATER) && api.once_in(POWDER_SOAKING) {
+        api.set(dx, dy, EMPTY_CELL);
+        api.set(0, 0, Cell::plain(wet));
+        return true;
+    }
+    false
+}
+
+// rb counts how far a wet powder has dried. heat dries it a little every tick
+// until it is left as `dry`, otherwise the wet cell is returned to move on
+fn dry_out(api: &mut Api, cell: Cell, dry: Species) -> Option<Cell> {
+    if !touching(api, is_hot) {
+        return Some(cell);
+    }
+    if cell.rb >= POWDER_DRYING {
+        if api.get(0, -1).species == Species::EMPT {
+            api.set(0, -1, Cell::plain(Species::STEAM));
+        }
+        api.set(0, 0, Cell::plain(dry));
+        return None;
+    }
+    let cell = Cell { rb: cell.rb + 1, ..cell };
+    api.set(0, 0, cell);
+    Some(cell)
+}
+
+pub fn update_dry_dust(cell: Cell, mut api: Api) {
+    if !soaks(&mut api, Species::MUD) {
+        update_dust(cell, api, 0);
+    }
+}
+
+pub fn update_dry_sand(cell: Cell, mut api: Api) {
+    if !soaks(&mut api, Species::WETSAND) {
+        update_sand(cell, api, 0);
+    }
+}
+
+pub fn update_wetsand(cell: Cell, mut api: Api) {
+    if let Some(cell) = dry_out(&mut api, cell, Species::SAND) {
+        update_sand(cell, api, WETSAND_COHESION);
+    }
+}
+
+pub fn update_mud(cell: Cell, mut api: Api) {
+    if let Some(cell) = dry_out(&mut api, cell, Species::DUST) {
+        update_dust(cell, api, MUD_COHESION);
+    }
+}
+
+pub fn update_water(cell: Cell, api: Api) {
+    update_liquid(cell, api, 0);
+}
+
+// liquid movement shared by water and lava. a liquid with a viscosity above 1
+// only slides sideways once in that many ticks, so it spreads out slowly
+fn update_liquid(cell: Cell, mut api: Api, viscosity: i32) {
     let mut dx = api.rand_dir();
     let below = api.get(0, 1);
     let dx1 = api.get(dx, 1);
@@ -114,6 +548,8 @@ pub fn update_water(cell: Cell, mut api: Api) {
         }
         api.set(0, 1, Cell { ra, ..cell });
 
+        return;
+    } else if viscosity > 1 && !api.once_in(viscosity) {
         return;
     } else if dx1.species == Species::EMPT || dx1.species == Species::OIL {
         //fall diagonally
@@ -138,7 +574,7 @@ pub fn update_water(cell: Cell, mut api: Api) {
         let nbr = api.get(dx, dy);
 
         // spread opinion
-        if nbr.species == Species::WATR {
+        if nbr.species == cell.species {
             if nbr.ra % 2 != cell.ra % 2 {
                 api.set(
                     dx,
@@ -155,7 +591,7 @@ pub fn update_water(cell: Cell, mut api: Api) {
         api.set(dx, 0, Cell { rb: 3, ..cell });
         let (dx, dy) = api.rand_vec_8();
         let nbr = api.get(dx, dy);
-        if nbr.species == Species::WATR {
+        if nbr.species == cell.species {
             if nbr.ra % 2 != cell.ra % 2 {
                 api.set(
                     dx,
@@ -216,6 +652,464 @@ pub fn update_water(cell: Cell, mut api: Api) {
     // }
     }
 
+pub fn update_lava(cell: Cell, mut api: Api) {
+    let stone = Cell::plain(Species::STNE);
+
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+    // water boils off and the lava touching it sets
+    if nbr.species == Species::WATR {
+        api.set(dx, dy, Cell::plain(Species::STEAM));
+        api.set(0, 0, stone);
+        return;
+    }
+    ignite(&mut api, dx, dy, nbr);
+
+    if api.once_in(LAVA_COOLING) {
+        api.set(0, 0, stone);
+        return;
+    }
+    // the odd wisp of smoke off the surface
+    if api.once_in(200) && api.get(0, -1).species == Species::EMPT {
+        api.set(0, -1, smoke(LAVA_SMOKE));
+    }
+    update_liquid(cell, api, LAVA_VISCOSITY);
+}
+
+// fire, lava and glowing charcoal melt ice and snow, dry out wet powders and
+// set off explosives
+fn is_hot(cell: Cell) -> bool {
+    match cell.species {
+        Species::FIRE | Species::LAVA => true,
+        Species::CHARCOAL => cell.rb > 1,
+        _ => false,
+    }
+}
+
+// set the neighbour at (dx, dy) alight if it burns, returns whether it did
+fn ignite(api: &mut Api, dx: i32, dy: i32, nbr: Cell) -> bool {
+    match nbr.species {
+        Species::GAS | Species::DUST | Species::SEED | Species::PLANT | Species::FLOWER => {
+            let ra = 150 + api.rand_int(50) as u8;
+            api.set(
+                dx,
+                dy,
+                Cell {
+                    species: Species::FIRE,
+                    ra,
+                    rb: 0,
+                    clock: 0,
+                },
+            );
+        }
+        // wood chars as it burns and goes on glowing long after the flames
+        Species::WOOD => api.set(dx, dy, lit_charcoal()),
+        Species::CHARCOAL if nbr.rb <= 1 => api.set(dx, dy, lit_charcoal()),
+        // oil burns the same way it does when fire reaches it
+        Species::OIL if nbr.rb == 0 => api.set(dx, dy, Cell { rb: 50, ..nbr }),
+        _ => return false,
+    }
+    true
+}
+
+fn lit_charcoal() -> Cell {
+    Cell {
+        rb: CHARCOAL_BURNING,
+        ..Cell::plain(Species::CHARCOAL)
+    }
+}
+
+// smoke of the given thickness, see FIRE_SMOKE and friends
+pub fn smoke(density: u8) -> Cell {
+    Cell {
+        ra: density,
+        ..Cell::plain(Species::SMKE)
+    }
+}
+
+fn water(api: &mut Api) -> Cell {
+    Cell {
+        ra: 100 + api.rand_int(50) as u8,
+        ..Cell::plain(Species::WATR)
+    }
+}
+
+pub fn update_steam(cell: Cell, mut api: Api) {
+    // touching anything frozen cools it straight away
+    let (nx, ny) = api.rand_vec_8();
+    let cold = matches!(api.get(nx, ny).species, Species::ICE | Species::SNOW);
+    let above = api.get(0, -1).species;
+    let ceiling = above != Species::EMPT && above != Species::STEAM;
+    if cold || api.once_in(if ceiling { STEAM_CEILING } else { STEAM_CONDENSING }) {
+        let water = water(&mut api);
+        api.set(0, 0, water);
+        return;
+    }
+
+    // rise, spreading out sideways when something is in the way
+    let dx = api.rand_dir();
+    if api.get(dx, -1).species == Species::EMPT {
+        api.set(dx, -1, cell);
+        api.set(0, 0, EMPTY_CELL);
+    } else if api.get(dx, 0).species == Species::EMPT {
+        api.set(dx, 0, cell);
+        api.set(0, 0, EMPTY_CELL);
+    }
+}
+
+// whether ice or snow that melts next to water once in `odds` ticks melts
+// next to `nbr`
+fn thaws(api: &mut Api, nbr: Cell, odds: i32) -> bool {
+    match nbr.species {
+        Species::WATR => api.once_in(odds),
+        Species::SALTWATER => api.once_in((odds / SALTWATER_THAWING).max(1)),
+        _ => is_hot(nbr),
+    }
+}
+
+pub fn update_ice(_cell: Cell, mut api: Api) {
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+    if thaws(&mut api, nbr, ICE_MELTING) {
+        let water = water(&mut api);
+        api.set(0, 0, water);
+    }
+}
+
+pub fn update_snow(cell: Cell, mut api: Api) {
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+    if thaws(&mut api, nbr, SNOW_MELTING) {
+        let water = water(&mut api);
+        api.set(0, 0, water);
+        return;
+    }
+
+    // buried under more snow it packs into ice
+    let above = api.get(0, -1).species;
+    let below = api.get(0, 1).species;
+    if above == Species::SNOW && (below == Species::SNOW || below == Species::ICE || below == Species::WALL) && api.once_in(SNOW_COMPACTING) {
+        api.set(0, 0, Cell::plain(Species::ICE));
+        return;
+    }
+
+    // drifts down like dust
+    update_dust(cell, api, 0);
+}
+
+// ticks, on average, acid has to touch a species to dissolve it. solids and
+// powders without one, walls among them, cannot be dissolved
+fn acid_resistance(species: Species) -> Option<i32> {
+    match species {
+        Species::SNOW | Species::FLOWER | Species::ASH | Species::SOOT => Some(2),
+        Species::MUD => Some(4),
+        Species::SEED | Species::PLANT => Some(3),
+        Species::DUST | Species::GOL | Species::GUNPOWDER => Some(4),
+        Species::ICE => Some(6),
+        Species::CHARCOAL => Some(8),
+        Species::SALT => Some(6),
+        Species::WOOD => Some(10),
+        Species::SAND | Species::WETSAND => Some(16),
+        Species::STNE | Species::METAL | Species::C4 => Some(40),
+        _ => None,
+    }
+}
+
+pub fn update_acid(cell: Cell, mut api: Api) {
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+
+    if matches!(nbr.species, Species::WATR | Species::SALTWATER) {
+        if api.once_in(ACID_DILUTING) {
+            let water = water(&mut api);
+            api.set(0, 0, water);
+            return;
+        }
+    } else if let Some(resistance) = acid_resistance(nbr.species) {
+        if api.once_in(resistance) {
+            // what dissolves fizzes off as gas, using up some of the acid
+            let fumes = if api.once_in(3) { Cell::plain(Species::GAS) } else { EMPTY_CELL };
+            api.set(dx, dy, fumes);
+            if api.once_in(2) {
+                api.set(0, 0, EMPTY_CELL);
+                return;
+            }
+        }
+    }
+    update_liquid(cell, api, 0);
+}
+
+// whether any of the eight neighbours is a cell `matches` picks out
+fn touching(api: &mut Api, matches: fn(Cell) -> bool) -> bool {
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            if (dx != 0 || dy != 0) && matches(api.get(dx, dy)) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// explosives go off next to heat or current
+fn sets_off(cell: Cell) -> bool {
+    is_hot(cell) || cell.species == Species::SPARK
+}
+
+pub fn update_gunpowder(cell: Cell, mut api: Api) {
+    if touching(&mut api, sets_off) {
+        api.set(0, 0, EMPTY_CELL);
+        api.explode(GUNPOWDER_BLAST);
+        return;
+    }
+    update_dust(cell, api, 0);
+}
+
+pub fn update_c4(_cell: Cell, mut api: Api) {
+    if touching(&mut api, sets_off) {
+        api.set(0, 0, EMPTY_CELL);
+        api.explode(C4_BLAST);
+    }
+}
+
+pub fn update_seed(cell: Cell, mut api: Api) {
+    if matches!(api.get(0, 1).species, Species::SAND | Species::WETSAND | Species::MUD)
+        && touching(&mut api, |cell| matches!(cell.species, Species::WATR | Species::SALTWATER))
+        && api.once_in(SEED_GERMINATING)
+    {
+        let energy = PLANT_ENERGY + api.rand_int(PLANT_ENERGY);
+        api.set(
+            0,
+            0,
+            Cell {
+                ra: energy as u8,
+                ..Cell::plain(Species::PLANT)
+            },
+        );
+        return;
+    }
+    update_dust(cell, api, 0);
+}
+
+// ra is the energy a plant cell has left to grow with, cells without any are
+// stem. rb is the direction a growing tip is heading in
+pub fn update_plant(cell: Cell, mut api: Api) {
+    // plants painted with the brush carry more energy than a grown plant ever
+    // stores, they start out like a fresh sprout instead
+    if cell.ra > PLANT_MAX_ENERGY {
+        let sprout = Cell {
+            ra: PLANT_ENERGY as u8,
+            ..cell
+        };
+        api.set(0, 0, sprout);
+        return;
+    }
+    let (dx, dy) = api.rand_vec_8();
+    if api.get(dx, dy).species == Species::WATR && cell.ra < PLANT_MAX_ENERGY && api.once_in(PLANT_DRINKING) {
+        // a stem that drinks starts growing again, sprouting a new shoot
+        api.set(dx, dy, EMPTY_CELL);
+        let ra = cell.ra.saturating_add(PLANT_WATER_ENERGY).min(PLANT_MAX_ENERGY);
+        api.set(0, 0, Cell { ra, ..cell });
+        return;
+    }
+    if cell.ra == 0 || !api.once_in(PLANT_GROWING) {
+        return;
+    }
+
+    let mut direction = cell.rb as usize % PLANT_DIRECTIONS.len();
+    if api.once_in(PLANT_TURNING) {
+        direction = api.rand_int(PLANT_DIRECTIONS.len() as i32) as usize;
+    }
+    let (gx, gy) = PLANT_DIRECTIONS[direction];
+    if api.get(gx, gy).species != Species::EMPT {
+        return;
+    }
+
+    let mut energy = cell.ra - 1;
+    if energy > 2 && api.once_in(PLANT_BRANCHING) {
+        let side = (direction + 1 + api.rand_int(2) as usize) % PLANT_DIRECTIONS.len();
+        let (bx, by) = PLANT_DIRECTIONS[side];
+        if api.get(bx, by).species == Species::EMPT {
+            let branch = energy / 2;
+            energy -= branch;
+            api.set(
+                bx,
+                by,
+                Cell {
+                    ra: branch,
+                    rb: side as u8,
+                    ..Cell::plain(Species::PLANT)
+                },
+            );
+        }
+    }
+    let tip = if energy == 0 && api.once_in(PLANT_FLOWERING) {
+        Cell::plain(Species::FLOWER)
+    } else {
+        Cell {
+            ra: energy,
+            rb: direction as u8,
+            ..Cell::plain(Species::PLANT)
+        }
+    };
+    api.set(gx, gy, tip);
+    api.set(0, 0, Cell { ra: 0, ..cell });
+}
+
+fn conducts(species: Species) -> bool {
+    matches!(species, Species::METAL | Species::SALTWATER)
+}
+
+// the head of a spark in the conductor `nbr`, which it turns back into once
+// the current has passed
+fn spark(nbr: Cell) -> Cell {
+    Cell {
+        rb: nbr.species as u8,
+        ..Cell::plain(Species::SPARK)
+    }
+}
+
+// ra counts the ticks since the spark arrived, up to SPARK_TAIL the spark is
+// tail and anything else, like a spark painted with the brush, is head. rb
+// is the conductor the spark runs through
+pub fn update_spark(cell: Cell, mut api: Api) {
+    if (1..SPARK_TAIL).contains(&cell.ra) {
+        api.set(0, 0, Cell { ra: cell.ra + 1, ..cell });
+        return;
+    }
+    if cell.ra == SPARK_TAIL {
+        let conductor = Species::all()
+            .get(cell.rb as usize)
+            .copied()
+            .filter(|species| conducts(*species))
+            .unwrap_or(Species::METAL);
+        api.set(0, 0, Cell::plain(conductor));
+        return;
+    }
+
+    // the head moves on into every conductor around it. tails are sparks
+    // rather than conductors, which keeps the current from flowing back
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let nbr = api.get(dx, dy);
+            if conducts(nbr.species) {
+                api.set(dx, dy, spark(nbr));
+            } else if nbr.species == Species::WATR {
+                if api.once_in(SPARK_ELECTROLYSING) {
+                    let gas = if api.once_in(3) { Species::OXGN } else { Species::HYGN };
+                    api.set(dx, dy, Cell::plain(gas));
+                }
+            } else if api.once_in(SPARK_IGNITING) {
+                ignite(&mut api, dx, dy, nbr);
+            }
+        }
+    }
+    api.set(0, 0, Cell { ra: 1, ..cell });
+}
+
+// rb counts the ticks since the last pulse
+pub fn update_battery(cell: Cell, mut api: Api) {
+    if cell.rb < BATTERY_PERIOD {
+        api.set(0, 0, Cell { rb: cell.rb + 1, ..cell });
+        return;
+    }
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let nbr = api.get(dx, dy);
+            if conducts(nbr.species) {
+                api.set(dx, dy, spark(nbr));
+            }
+        }
+    }
+    api.set(0, 0, Cell { rb: 0, ..cell });
+}
+
+// species clones can pick up, and voids swallow
+fn is_matter(species: Species) -> bool {
+    !matches!(species, Species::EMPT | Species::WALL | Species::CLONE | Species::VOID)
+}
+
+// rb is the species the clone learned and ra the state it copied, a clone
+// that has not learned anything yet takes on the first neighbour it sees
+pub fn update_clone(cell: Cell, mut api: Api) {
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+    let learned = Species::all().get(cell.rb as usize).copied().filter(|species| is_matter(*species));
+    match learned {
+        None if is_matter(nbr.species) => api.set(
+            0,
+            0,
+            Cell {
+                ra: nbr.ra,
+                rb: nbr.species as u8,
+                ..cell
+            },
+        ),
+        Some(species) if nbr.species == Species::EMPT => api.set(
+            dx,
+            dy,
+            Cell {
+                ra: cell.ra,
+                ..Cell::plain(species)
+            },
+        ),
+        _ => {}
+    }
+}
+
+pub fn update_void(_cell: Cell, mut api: Api) {
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            if is_matter(api.get(dx, dy).species) {
+                api.set(dx, dy, EMPTY_CELL);
+            }
+        }
+    }
+}
+
+pub fn update_salt(cell: Cell, mut api: Api) {
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+    let dissolves = match nbr.species {
+        Species::WATR => api.once_in(SALT_DISSOLVING),
+        Species::ICE | Species::SNOW => api.once_in(SALT_THAWING),
+        _ => false,
+    };
+    if dissolves {
+        let ra = if nbr.species == Species::WATR { nbr.ra } else { 100 + api.rand_int(50) as u8 };
+        api.set(
+            dx,
+            dy,
+            Cell {
+                ra,
+                ..Cell::plain(Species::SALTWATER)
+            },
+        );
+        api.set(0, 0, EMPTY_CELL);
+        return;
+    }
+    update_sand(cell, api, 0);
+}
+
+pub fn update_saltwater(cell: Cell, mut api: Api) {
+    let (dx, dy) = api.rand_vec_8();
+    if is_hot(api.get(dx, dy)) {
+        // the water boils off and leaves the salt behind
+        if api.get(0, -1).species == Species::EMPT {
+            api.set(0, -1, Cell::plain(Species::STEAM));
+        }
+        api.set(0, 0, Cell::plain(Species::SALT));
+        return;
+    }
+    let below = api.get(0, 1);
+    if below.species == Species::WATR && api.once_in(SALTWATER_SINKING) {
+        api.set(0, 0, below);
+        api.set(0, 1, cell);
+        return;
+    }
+    update_liquid(cell, api, 0);
+}
+
 pub fn update_fire(cell: Cell, mut api: Api) {
     let ra = cell.ra;
     let mut degraded = cell.clone();
@@ -230,17 +1124,8 @@ pub fn update_fire(cell: Cell, mut api: Api) {
         pressure: 1,
         density: 120,
     });
-    if api.get(dx, dy).species == Species::GAS || api.get(dx, dy).species == Species::DUST {
-        api.set(
-            dx,
-            dy,
-            Cell {
-                species: Species::FIRE,
-                ra: (150 + (dx + dy) * 10) as u8,
-                rb: 0,
-                clock: 0,
-            },
-        );
+    let nbr = api.get(dx, dy);
+    if nbr.species != Species::OIL && ignite(&mut api, dx, dy, nbr) {
         api.set_fluid(Wind {
             dx: 0,
             dy: 0,
@@ -248,8 +1133,13 @@ pub fn update_fire(cell: Cell, mut api: Api) {
             density: 40,
         });
     }
-    if ra < 5 || api.get(dx, dy).species == Species::WATR {
+    if api.get(dx, dy).species == Species::WATR {
+        // put out, boiling the water it touched
+        api.set(dx, dy, Cell::plain(Species::STEAM));
         api.set(0, 0, EMPTY_CELL);
+    } else if ra < 5 {
+        let embers = if api.once_in(4) { smoke(FIRE_SMOKE) } else { EMPTY_CELL };
+        api.set(0, 0, embers);
     } else if api.get(dx, dy).species == Species::EMPT {
         api.set(0, 0, EMPTY_CELL);
         api.set(dx, dy, degraded);
@@ -259,17 +1149,105 @@ pub fn update_fire(cell: Cell, mut api: Api) {
     }
 
 
+// ra is how thick the smoke is, 0 standing for SMOKE_DENSITY. it thins out a
+// little every tick and is gone once there is nothing left of it
 pub fn update_smoke(cell: Cell, mut api: Api) {
+    let density = if cell.ra == 0 { SMOKE_DENSITY } else { cell.ra };
+    if density == 1 {
+        api.set(0, 0, EMPTY_CELL);
+        return;
+    }
+    let thinner = Cell { ra: density - 1, ..cell };
     let dx = api.rand_dir();
     let nu = api.get(dx, -1);
 
     if nu.species == Species::EMPT {
-        api.set(dx, -1, cell);
+        api.set(dx, -1, thinner);
         api.set(0, 0, EMPTY_CELL);
+    } else {
+        api.set(0, 0, thinner);
+    }
+}
+
+// rb counts down the ticks lit charcoal has left to burn, at 0 or 1 it is out
+pub fn update_charcoal(cell: Cell, mut api: Api) {
+    if cell.rb <= 1 {
+        return;
+    }
+    let (dx, dy) = api.rand_vec_8();
+    let nbr = api.get(dx, dy);
+    if nbr.species == Species::WATR {
+        api.set(dx, dy, Cell::plain(Species::STEAM));
+        api.set(0, 0, Cell { rb: 0, ..cell });
+        return;
+    }
+    if !ignite(&mut api, dx, dy, nbr) && nbr.species == Species::EMPT && dy < 0 {
+        if api.once_in(CHARCOAL_SMOKING) {
+            api.set(dx, dy, smoke(CHARCOAL_SMOKE));
+        } else if api.once_in(CHARCOAL_FLAMING) {
+            let ra = 20 + api.rand_int(30) as u8;
+            api.set(
+                dx,
+                dy,
+                Cell {
+                    ra,
+                    ..Cell::plain(Species::FIRE)
+                },
+            );
+        }
     }
 
-    // die after 100 ticks
-    if cell.clock > 100 {
+    if cell.rb == 2 {
+        api.set(0, 0, Cell::plain(Species::ASH));
+    } else if api.once_in(2) {
+        api.set(0, 0, Cell { rb: cell.rb - 1, ..cell });
+    }
+}
+
+pub fn update_ash(cell: Cell, mut api: Api) {
+    // drifts down at half the speed of dust
+    if api.once_in(2) {
+        update_dust(cell, api, 0);
+    }
+}
+
+// solids that keep nothing in their rb, so soot can mark them as stained there
+fn stains(species: Species) -> bool {
+    matches!(species, Species::WALL | Species::WOOD | Species::STNE | Species::METAL | Species::ICE)
+}
+
+pub fn is_stained(cell: Cell) -> bool {
+    stains(cell.species) && cell.rb == SOOT_STAIN
+}
+
+// soot drifts up off burning oil and sticks to the first thing it touches,
+// staining the solids around it black. water washes it away
+pub fn update_soot(cell: Cell, mut api: Api) {
+    let mut stuck = false;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            match api.get(dx, dy).species {
+                Species::WATR => {
+                    api.set(0, 0, EMPTY_CELL);
+                    return;
+                }
+                Species::EMPT | Species::SOOT | Species::SMKE | Species::FIRE | Species::STEAM => {}
+                _ => stuck = true,
+            }
+        }
+    }
+    if stuck {
+        let (dx, dy) = api.rand_vec_8();
+        let nbr = api.get(dx, dy);
+        if stains(nbr.species) && !is_stained(nbr) && api.once_in(SOOT_STAINING) {
+            api.set(dx, dy, Cell { rb: SOOT_STAIN, ..nbr });
+        }
+        return;
+    }
+    let dx = api.rand_dir();
+    let dy = if api.once_in(3) { 1 } else { -1 };
+    if api.get(dx, dy).species == Species::EMPT {
+        api.set(dx, dy, cell);
         api.set(0, 0, EMPTY_CELL);
     }
 }
@@ -396,6 +1374,8 @@ fn update_oil(cell: Cell, mut api: Api) {
                     clock: 0,
                 },
             );
+        } else if nbr.species == Species::EMPT && api.once_in(4) {
+            api.set(dx, dy, smoke(OIL_SMOKE));
         }
         if nbr.species == Species::WATR {
             new_cell = Cell {
@@ -406,6 +1386,10 @@ fn update_oil(cell: Cell, mut api: Api) {
             };
         }
     } else if rb == 1 {
+        if api.once_in(OIL_SOOTING) {
+            api.set(0, 0, Cell::plain(Species::SOOT));
+            return;
+        }
         api.set(
             0,
             0,
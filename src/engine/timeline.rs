@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use rand_xoshiro::SplitMix64;
+
+use super::{Cell, Wind, World};
+
+// ticks between two snapshots
+const SNAPSHOT_INTERVAL: u64 = 30;
+// about 30 seconds of history at 60 ticks per second
+const MAX_SNAPSHOTS: usize = 60;
+
+// the state of the world at a tick, with runs of identical cells and winds
+// stored once
+struct Snapshot {
+    tick: u64,
+    runs: Vec<(u32, Cell)>,
+    winds: Vec<(u32, Wind)>,
+    gusts: Vec<usize>,
+    generation: u8,
+    rng: SplitMix64,
+}
+
+fn runs<T: Copy + PartialEq>(items: &[T]) -> Vec<(u32, T)> {
+    let mut runs: Vec<(u32, T)> = Vec::new();
+    for item in items.iter() {
+        match runs.last_mut() {
+            Some((count, last)) if last == item => *count += 1,
+            _ => runs.push((1, *item)),
+        }
+    }
+    runs
+}
+
+fn unroll<T: Copy>(runs: &[(u32, T)], items: &mut [T]) {
+    let mut i = 0;
+    for (count, item) in runs.iter() {
+        let count = *count as usize;
+        items[i..i + count].fill(*item);
+        i += count;
+    }
+}
+
+impl World {
+    fn snapshot(&self, tick: u64) -> Snapshot {
+        Snapshot {
+            tick,
+            runs: runs(&self.cells),
+            winds: runs(&self.winds),
+            gusts: self.gusts.clone(),
+            generation: self.generation,
+            rng: self.rng.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        unroll(&snapshot.runs, &mut self.cells);
+        unroll(&snapshot.winds, &mut self.winds);
+        self.gusts = snapshot.gusts.clone();
+        self.generation = snapshot.generation;
+        self.rng = snapshot.rng.clone();
+        self.edits.clear();
+    }
+}
+
+// records periodic snapshots of the world together with the edits made in
+// between, so that any recent tick can be rebuilt. ticking is deterministic
+// given the world rng, replaying a snapshot with its edits gives the same result
+pub struct Timeline {
+    // ticks simulated since the start
+    tick: u64,
+    snapshots: VecDeque<Snapshot>,
+    // edits made after the given tick finished, oldest first
+    edits: VecDeque<(u64, Vec<(usize, Cell)>)>,
+}
+
+impl Timeline {
+    pub fn new() -> Timeline {
+        Timeline {
+            tick: 0,
+            snapshots: VecDeque::new(),
+            edits: VecDeque::new(),
+        }
+    }
+
+    pub fn tick_count(&self) -> u64 {
+        self.tick
+    }
+
+    // advance the world by one tick, recording what is needed to come back later
+    pub fn tick(&mut self, world: &mut World) {
+        let edits = world.take_edits();
+        if !edits.is_empty() {
+            self.edits.push_back((self.tick, edits));
+        }
+
+        let taken = self.snapshots.back().is_some_and(|s| s.tick == self.tick);
+        if self.tick.is_multiple_of(SNAPSHOT_INTERVAL) && !taken {
+            self.snapshots.push_back(world.snapshot(self.tick));
+            if self.snapshots.len() > MAX_SNAPSHOTS {
+                self.snapshots.pop_front();
+            }
+            let oldest = self.snapshots[0].tick;
+            while self.edits.front().is_some_and(|(tick, _)| *tick < oldest) {
+                self.edits.pop_front();
+            }
+        }
+
+        world.tick();
+        self.tick += 1;
+    }
+
+    // jump back to the closest snapshot before the current tick
+    pub fn rewind(&mut self, world: &mut World) {
+        if let Some(snapshot) = self.snapshots.iter().rev().find(|s| s.tick < self.tick) {
+            let tick = snapshot.tick;
+            self.seek(world, tick);
+        }
+    }
+
+    // go back a single tick, replaying from the snapshot before it
+    pub fn step_back(&mut self, world: &mut World) {
+        if self.tick > 0 {
+            self.seek(world, self.tick - 1);
+        }
+    }
+
+    // rebuild the world as it was at `target`, anything after it is forgotten
+    pub fn seek(&mut self, world: &mut World, target: u64) {
+        let Some(snapshot) = self.snapshots.iter().rev().find(|s| s.tick <= target) else {
+            return;
+        };
+        world.restore(snapshot);
+        // edits made right after an earlier seek to this snapshot come after it
+        // was taken. they are absolute writes, putting back the rest is harmless
+        for (_, edits) in self.edits.iter().filter(|(t, _)| *t == snapshot.tick) {
+            for &(i, cell) in edits {
+                world.cells[i] = cell;
+            }
+        }
+
+        for tick in snapshot.tick..target {
+            world.tick();
+            for (_, edits) in self.edits.iter().filter(|(t, _)| *t == tick + 1) {
+                for &(i, cell) in edits {
+                    world.cells[i] = cell;
+                }
+            }
+        }
+
+        self.snapshots.retain(|s| s.tick <= target);
+        self.edits.retain(|(tick, _)| *tick <= target);
+        self.tick = target;
+    }
+}
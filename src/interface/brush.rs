@@ -0,0 +1,390 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::engine::species::Species;
+use crate::engine::history::History;
+use crate::engine::{Cell, World};
+
+use super::camera::Camera;
+
+pub const MAX_BRUSH_SIZE: i32 = 300;
+pub const TOOL_COUNT: usize = 8;
+// chance for the spray tool to place a cell under the brush each frame
+const SPRAY_ONE_IN: i32 = 12;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Tool {
+    // freehand brushes, hold shift while dragging to draw a straight line
+    Circle,
+    Square,
+    // drag out a filled or hollow rectangle
+    Rect,
+    HollowRect,
+    // replace the contiguous region under the cursor
+    Fill,
+    // place cells under a circle with a probability
+    Spray,
+    // circle brushes laying down clones of the species, which pour it out
+    // forever, or voids that swallow whatever reaches them
+    Tap,
+    Drain,
+}
+
+impl Tool {
+    pub fn all() -> [Tool; TOOL_COUNT] {
+        [
+            Tool::Circle,
+            Tool::Square,
+            Tool::Rect,
+            Tool::HollowRect,
+            Tool::Fill,
+            Tool::Spray,
+            Tool::Tap,
+            Tool::Drain,
+        ]
+    }
+
+    // short name shown in the side bar
+    pub fn label(&self) -> &'static str {
+        match self {
+            Tool::Circle => "CIRC",
+            Tool::Square => "SQR",
+            Tool::Rect => "RECT",
+            Tool::HollowRect => "HRCT",
+            Tool::Fill => "FILL",
+            Tool::Spray => "SPRY",
+            Tool::Tap => "TAP",
+            Tool::Drain => "DRN",
+        }
+    }
+
+    pub fn next(&self) -> Tool {
+        let all = Tool::all();
+        let i = all.iter().position(|tool| tool == self).unwrap_or(0);
+        all[(i + 1) % TOOL_COUNT]
+    }
+}
+
+// which cells the brush is allowed to paint over
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Mode {
+    // only empty cells
+    Empty,
+    // anything
+    Replace,
+    // only cells of the given species, e.g. to turn water into oil
+    Target(Species),
+}
+
+impl Mode {
+    pub fn label(&self) -> String {
+        match self {
+            Mode::Empty => "EMPT".to_string(),
+            Mode::Replace => "ALL".to_string(),
+            Mode::Target(species) => format!("={:?}", species),
+        }
+    }
+
+    // cycle through the modes, the target mode starts out replacing water
+    pub fn next(&self) -> Mode {
+        match self {
+            Mode::Empty => Mode::Replace,
+            Mode::Replace => Mode::Target(Species::WATR),
+            Mode::Target(_) => Mode::Empty,
+        }
+    }
+
+    // erasing ignores the empty-only rule, there would be nothing to erase otherwise
+    fn allows(&self, current: Species, species: Species) -> bool {
+        match self {
+            Mode::Target(target) => current == *target,
+            Mode::Empty if species != Species::EMPT => current == Species::EMPT,
+            _ => true,
+        }
+    }
+}
+
+pub struct Brush {
+    pub tool: Tool,
+    pub mode: Mode,
+    // brush diameter in cells
+    pub size: i32,
+    // cell where the current drag started
+    start: Option<(i32, i32)>,
+    // species the current drag paints with, empty when erasing
+    stroke: Species,
+    // cell painted on the previous frame, to fill the gaps of fast strokes
+    last: Option<(i32, i32)>,
+    // the current drag is a straight line
+    line: bool,
+}
+
+impl Brush {
+    pub fn new() -> Brush {
+        Brush {
+            tool: Tool::Circle,
+            mode: Mode::Empty,
+            size: 3,
+            start: None,
+            stroke: Species::EMPT,
+            last: None,
+            line: false,
+        }
+    }
+
+    pub fn resize(&mut self, delta: i32) {
+        self.size = (self.size + delta).clamp(1, MAX_BRUSH_SIZE);
+    }
+
+    // advance the brush by a frame. `pressed` starts a stroke painting the given
+    // species, it then goes on for as long as `down` is held and is finished on
+    // release. every stroke is recorded as one edit in the history
+    pub fn update(&mut self, world: &mut World, history: &mut History, (x, y): (i32, i32), pressed: Option<Species>, down: bool, shift: bool) {
+        if let Some(species) = pressed {
+            history.begin();
+            self.start = Some((x, y));
+            self.stroke = species;
+            self.last = None;
+            self.line = shift;
+            if self.tool == Tool::Fill {
+                self.flood_fill(world, history, x, y, species);
+            }
+        }
+        let species = self.stroke;
+
+        let Some(start) = self.start else {
+            return;
+        };
+
+        if down {
+            match self.tool {
+                Tool::Circle | Tool::Square | Tool::Tap | Tool::Drain if !self.line => {
+                    let from = self.last.unwrap_or((x, y));
+                    for (px, py) in line_points(from, (x, y)) {
+                        self.stamp(world, history, px, py, species);
+                    }
+                    self.last = Some((x, y));
+                }
+                Tool::Spray => self.spray(world, history, x, y, species),
+                _ => {}
+            }
+            return;
+        }
+
+        // released, finish shapes that are only placed at the end of the drag
+        match self.tool {
+            // pressed and released within a frame, nothing was stamped yet
+            Tool::Circle | Tool::Square | Tool::Tap | Tool::Drain if self.last.is_none() => {
+                self.stamp(world, history, x, y, species)
+            }
+            Tool::Circle | Tool::Square | Tool::Tap | Tool::Drain if self.line => {
+                for (px, py) in line_points(start, (x, y)) {
+                    self.stamp(world, history, px, py, species);
+                }
+            }
+            Tool::Rect | Tool::HollowRect => {
+                for (px, py) in rect_points(start, (x, y), self.tool == Tool::HollowRect) {
+                    self.place(world, history, px, py, species);
+                }
+            }
+            _ => {}
+        }
+        history.commit();
+        self.start = None;
+        self.last = None;
+        self.line = false;
+    }
+
+    fn stamp(&self, world: &mut World, history: &mut History, x: i32, y: i32, species: Species) {
+        for (px, py) in self.footprint(x, y) {
+            self.place(world, history, px, py, species);
+        }
+    }
+
+    fn spray(&self, world: &mut World, history: &mut History, x: i32, y: i32, species: Species) {
+        for (px, py) in circle_points(x, y, self.size) {
+            if rand::thread_rng().gen_range(0..SPRAY_ONE_IN) == 0 {
+                self.place(world, history, px, py, species);
+            }
+        }
+    }
+
+    fn can_place(&self, world: &World, x: i32, y: i32, species: Species) -> bool {
+        self.mode.allows(world.get(x as usize, y as usize).species, species)
+    }
+
+    // put a freshly painted cell at (x, y) if the mode allows painting over what is there
+    fn place(&self, world: &mut World, history: &mut History, x: i32, y: i32, species: Species) {
+        if x < 0 || x > world.width() - 1 || y < 0 || y > world.height() - 1 {
+            return;
+        }
+        if !self.can_place(world, x, y, species) {
+            return;
+        }
+        // erasing with the tap or drain erases like any other brush
+        let cell = match self.tool {
+            Tool::Tap if species != Species::EMPT => (Species::CLONE, species as u8),
+            Tool::Drain if species != Species::EMPT => (Species::VOID, 1),
+            _ => (species, 1),
+        };
+        paint(world, history, x, y, cell, self.size);
+    }
+
+    // replace the 4-connected region of the same species as (x, y)
+    fn flood_fill(&self, world: &mut World, history: &mut History, x: i32, y: i32, species: Species) {
+        if x < 0 || x > world.width() - 1 || y < 0 || y > world.height() - 1 {
+            return;
+        }
+        let target = world.get(x as usize, y as usize).species;
+        if target == species || !self.can_place(world, x, y, species) {
+            return;
+        }
+
+        let mut queue = VecDeque::new();
+        queue.push_back((x, y));
+        paint(world, history, x, y, (species, 1), self.size);
+        while let Some((cx, cy)) = queue.pop_front() {
+            for (nx, ny) in [(cx + 1, cy), (cx - 1, cy), (cx, cy + 1), (cx, cy - 1)] {
+                if nx < 0 || nx > world.width() - 1 || ny < 0 || ny > world.height() - 1 {
+                    continue;
+                }
+                if world.get(nx as usize, ny as usize).species != target {
+                    continue;
+                }
+                // painted cells no longer match the target, so nothing is queued twice
+                paint(world, history, nx, ny, (species, 1), self.size);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    // cells covered by the brush tip centred on (x, y)
+    fn footprint(&self, x: i32, y: i32) -> Vec<(i32, i32)> {
+        match self.tool {
+            Tool::Square => {
+                let half = self.size / 2;
+                rect_points((x - half, y - half), (x - half + self.size - 1, y - half + self.size - 1), false)
+            }
+            Tool::Rect | Tool::HollowRect | Tool::Fill => vec![(x, y)],
+            Tool::Circle | Tool::Spray | Tool::Tap | Tool::Drain => circle_points(x, y, self.size),
+        }
+    }
+
+    // outline the cells the brush would affect, including the shape being dragged out
+    pub fn draw_cursor(&self, canvas: &mut Canvas<Window>, camera: &Camera, world: &World, (x, y): (i32, i32), species: Species) {
+        let mut cells = match (self.start, self.tool) {
+            (Some(start), Tool::Circle | Tool::Square | Tool::Tap | Tool::Drain) if self.line => line_points(start, (x, y))
+                .into_iter()
+                .flat_map(|(px, py)| self.footprint(px, py))
+                .collect(),
+            (Some(start), Tool::Rect | Tool::HollowRect) => rect_points(start, (x, y), self.tool == Tool::HollowRect),
+            _ => self.footprint(x, y),
+        };
+        cells.sort_unstable();
+        cells.dedup();
+
+        canvas.set_draw_color(Color::RGB(255, 255, 255));
+        for (px, py) in cells {
+            if px < 0 || px > world.width() - 1 || py < 0 || py > world.height() - 1 {
+                continue;
+            }
+            let (sx, sy) = camera.world_to_screen(px, py);
+            if !camera.contains_screen(sx, sy) {
+                continue;
+            }
+            if self.can_place(world, px, py, species) {
+                canvas
+                    .fill_rect(Rect::new(sx, sy, camera.zoom as u32, camera.zoom as u32))
+                    .unwrap();
+            }
+        }
+    }
+}
+
+// painting keeps its hands off the world rng so the simulation stays
+// reproducible from recorded edits
+fn paint(world: &mut World, history: &mut History, x: i32, y: i32, (species, rb): (Species, u8), size: i32) {
+    let ra = 60u8
+        .wrapping_add(size as u8)
+        .wrapping_add((rand::thread_rng().gen::<f32>() * 30.) as u8)
+        .wrapping_add(((world.generation % 127) as i8 - 60).unsigned_abs());
+    let clock = world.generation;
+    // oil keeps how long it has left to burn in rb, where 1 means burnt out
+    let rb = if species == Species::OIL { 0 } else { rb };
+    history.set(
+        world,
+        x as usize,
+        y as usize,
+        Cell {
+            species,
+            ra,
+            rb,
+            clock,
+        },
+    );
+}
+
+fn circle_points(x: i32, y: i32, size: i32) -> Vec<(i32, i32)> {
+    let radius: f64 = (size as f64) / 2.0;
+    let floor = (radius + 1.0) as i32;
+    let ciel = (radius + 1.5) as i32;
+
+    let mut points = Vec::new();
+    for dx in -floor..ciel {
+        for dy in -floor..ciel {
+            if (((dx * dx) + (dy * dy)) as f64) > (radius * radius) {
+                continue;
+            };
+            points.push((x + dx, y + dy));
+        }
+    }
+    points
+}
+
+// cells inside the rectangle spanned by two corners, or only its border when hollow
+fn rect_points((x0, y0): (i32, i32), (x1, y1): (i32, i32), hollow: bool) -> Vec<(i32, i32)> {
+    let (left, right) = (x0.min(x1), x0.max(x1));
+    let (top, bottom) = (y0.min(y1), y0.max(y1));
+
+    let mut points = Vec::new();
+    for y in top..=bottom {
+        for x in left..=right {
+            if !hollow || x == left || x == right || y == top || y == bottom {
+                points.push((x, y));
+            }
+        }
+    }
+    points
+}
+
+// bresenham line between two cells, both ends included
+fn line_points((mut x0, mut y0): (i32, i32), (x1, y1): (i32, i32)) -> Vec<(i32, i32)> {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let mut points = Vec::new();
+    loop {
+        points.push((x0, y0));
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+    }
+    points
+}
@@ -0,0 +1,81 @@
+pub const MIN_ZOOM: i32 = 1;
+pub const MAX_ZOOM: i32 = 8;
+
+// maps between screen coordinates in the world viewport and cell coordinates
+pub struct Camera {
+    // world cell shown at the top left corner of the viewport
+    pub x: i32,
+    pub y: i32,
+    // screen pixels per cell
+    pub zoom: i32,
+    // size of the viewport in screen pixels
+    pub view_width: i32,
+    pub view_height: i32,
+}
+
+impl Camera {
+    pub fn new(view_width: i32, view_height: i32) -> Camera {
+        Camera {
+            x: 0,
+            y: 0,
+            zoom: MIN_ZOOM,
+            view_width,
+            view_height,
+        }
+    }
+
+    pub fn screen_to_world(&self, sx: i32, sy: i32) -> (i32, i32) {
+        (
+            self.x + sx.div_euclid(self.zoom),
+            self.y + sy.div_euclid(self.zoom),
+        )
+    }
+
+    pub fn world_to_screen(&self, wx: i32, wy: i32) -> (i32, i32) {
+        ((wx - self.x) * self.zoom, (wy - self.y) * self.zoom)
+    }
+
+    pub fn contains_screen(&self, sx: i32, sy: i32) -> bool {
+        sx >= 0 && sx < self.view_width && sy >= 0 && sy < self.view_height
+    }
+
+    // number of cells visible along each axis
+    pub fn visible_cells(&self) -> (i32, i32) {
+        (
+            (self.view_width + self.zoom - 1) / self.zoom,
+            (self.view_height + self.zoom - 1) / self.zoom,
+        )
+    }
+
+    // move the camera by a distance in screen pixels
+    pub fn pan(&mut self, dx: i32, dy: i32, world_width: i32, world_height: i32) {
+        self.x += dx / self.zoom;
+        self.y += dy / self.zoom;
+        self.clamp(world_width, world_height);
+    }
+
+    // change the zoom level while keeping the cell under (sx, sy) in place
+    pub fn zoom_at(&mut self, sx: i32, sy: i32, delta: i32, world_width: i32, world_height: i32) {
+        let zoom = (self.zoom + delta).clamp(MIN_ZOOM, MAX_ZOOM);
+        if zoom == self.zoom {
+            return;
+        }
+        let (wx, wy) = self.screen_to_world(sx, sy);
+        self.zoom = zoom;
+        self.anchor(wx, wy, sx, sy, world_width, world_height);
+    }
+
+    // move the camera so that cell (wx, wy) ends up under screen point (sx, sy)
+    pub fn anchor(&mut self, wx: i32, wy: i32, sx: i32, sy: i32, world_width: i32, world_height: i32) {
+        self.x = wx - sx / self.zoom;
+        self.y = wy - sy / self.zoom;
+        self.clamp(world_width, world_height);
+    }
+
+    // keep the viewport inside the world, worlds smaller than the viewport stay at the origin
+    pub fn clamp(&mut self, world_width: i32, world_height: i32) {
+        let (cells_x, cells_y) = self.visible_cells();
+        self.x = self.x.min(world_width - cells_x).max(0);
+        self.y = self.y.min(world_height - cells_y).max(0);
+    }
+}
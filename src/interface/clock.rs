@@ -0,0 +1,75 @@
+use std::time::Instant;
+
+// simulation ticks per second at 1x speed
+pub const TICK_RATE: f64 = 60.0;
+pub const SPEEDS: [f64; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+// upper bound on ticks run in one frame, so a slow frame does not snowball
+const MAX_TICKS_PER_FRAME: u32 = 32;
+
+// decides how many ticks to run each frame, independent of the frame rate
+pub struct SimClock {
+    speed_index: usize,
+    // simulated time owed to the world
+    accumulator: f64,
+    last: Instant,
+    // ticks per second are never above this, whatever the speed
+    max_rate: Option<f64>,
+}
+
+impl SimClock {
+    pub fn new(max_rate: Option<f64>) -> SimClock {
+        SimClock {
+            speed_index: 2,
+            accumulator: 0.0,
+            last: Instant::now(),
+            max_rate,
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed_index]
+    }
+
+    pub fn speed_index(&self) -> usize {
+        self.speed_index
+    }
+
+    pub fn set_speed_index(&mut self, index: usize) {
+        self.speed_index = index.min(SPEEDS.len() - 1);
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    // ticks to run for the time that passed since the last call. time the
+    // simulation is not running forward, paused or rewinding, is dropped
+    // instead of being caught up on afterwards
+    pub fn ticks_due(&mut self, paused: bool) -> u32 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last);
+        self.last = now;
+        if paused {
+            self.accumulator = 0.0;
+            return 0;
+        }
+
+        let tick_length = 1.0 / TICK_RATE;
+        let speed = match self.max_rate {
+            Some(max_rate) => self.speed().min(max_rate / TICK_RATE),
+            None => self.speed(),
+        };
+        self.accumulator += elapsed.as_secs_f64() * speed;
+        let ticks = (self.accumulator / tick_length) as u32;
+        self.accumulator -= ticks as f64 * tick_length;
+        if ticks > MAX_TICKS_PER_FRAME {
+            self.accumulator = 0.0;
+            return MAX_TICKS_PER_FRAME;
+        }
+        ticks
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, Texture, TextureCreator};
+use sdl2::ttf::Font;
+use sdl2::video::{Window, WindowContext};
+
+// a rendered character and how far to move right after drawing it
+struct Glyph<'a> {
+    texture: Option<Texture<'a>>,
+    advance: i32,
+}
+
+// draws text one cached glyph texture at a time instead of rendering a new
+// surface and texture for every string on every frame. glyphs are rendered
+// white and tinted with a color mod, so one texture serves every text color
+pub struct Glyphs<'a> {
+    font: Font<'a, 'static>,
+    texture_creator: &'a TextureCreator<WindowContext>,
+    glyphs: RefCell<HashMap<char, Glyph<'a>>>,
+}
+
+impl<'a> Glyphs<'a> {
+    pub fn new(font: Font<'a, 'static>, texture_creator: &'a TextureCreator<WindowContext>) -> Glyphs<'a> {
+        Glyphs {
+            font,
+            texture_creator,
+            glyphs: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        self.font.height().max(0) as u32
+    }
+
+    fn load(&self, c: char) {
+        if self.glyphs.borrow().contains_key(&c) {
+            return;
+        }
+        // characters missing from the font still take up space, but draw nothing
+        let advance = self.font.find_glyph_metrics(c).map_or(0, |metrics| metrics.advance);
+        let texture = self
+            .font
+            .render_char(c)
+            .blended(Color::RGB(255, 255, 255))
+            .ok()
+            .and_then(|surface| self.texture_creator.create_texture_from_surface(&surface).ok());
+        self.glyphs.borrow_mut().insert(c, Glyph { texture, advance });
+    }
+
+    pub fn width_of(&self, text: &str) -> u32 {
+        text.chars()
+            .map(|c| {
+                self.load(c);
+                self.glyphs.borrow()[&c].advance
+            })
+            .sum::<i32>()
+            .max(0) as u32
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas<Window>, text: &str, x: i32, y: i32, color: Color) {
+        let mut x = x;
+        for c in text.chars() {
+            self.load(c);
+            let mut glyphs = self.glyphs.borrow_mut();
+            let glyph = glyphs.get_mut(&c).expect("glyph was just loaded");
+            if let Some(texture) = glyph.texture.as_mut() {
+                texture.set_color_mod(color.r, color.g, color.b);
+                let query = texture.query();
+                canvas
+                    .copy(texture, None, Rect::new(x, y, query.width, query.height))
+                    .expect("Failed to copy glyph");
+            }
+            x += glyph.advance;
+        }
+    }
+}
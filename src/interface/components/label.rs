@@ -0,0 +1,24 @@
+use sdl2::pixels::Color;
+
+use super::{Ui, TEXT_COLOR};
+
+pub struct Label<'t> {
+    x: i32,
+    y: i32,
+    text: &'t str,
+    color: Color,
+}
+
+impl<'t> Label<'t> {
+    pub fn new(x: i32, y: i32, text: &'t str) -> Label<'t> {
+        Label { x, y, text, color: TEXT_COLOR }
+    }
+
+    pub fn color(self, color: Color) -> Label<'t> {
+        Label { color, ..self }
+    }
+
+    pub fn show(&self, ui: &mut Ui) {
+        ui.text(self.text, self.x, self.y, self.color);
+    }
+}
@@ -1 +1,94 @@
-pub mod button;
\ No newline at end of file
+use sdl2::mouse::MouseButton;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use self::glyphs::Glyphs;
+use super::input::InputState;
+
+pub mod button;
+pub mod glyphs;
+pub mod label;
+pub mod panel;
+pub mod slider;
+pub mod toggle;
+
+pub const TEXT_COLOR: Color = Color::RGB(150, 150, 150);
+const HOVER_COLOR: Color = Color::RGB(255, 0, 0);
+const ACTIVE_COLOR: Color = Color::RGB(255, 255, 255);
+const PRESSED_COLOR: Color = Color::RGB(60, 60, 60);
+
+// how the mouse relates to a widget this frame
+#[derive(Clone, Copy, PartialEq)]
+pub enum State {
+    Idle,
+    Hovered,
+    // hovered with the button held down
+    Pressed,
+}
+
+// everything widgets need to draw themselves and react to the mouse for one
+// frame. widgets are immediate mode: they are created, shown and dropped every
+// frame, and report what happened to them to the caller
+pub struct Ui<'a, 'b> {
+    pub canvas: &'a mut Canvas<Window>,
+    pub font: &'a Glyphs<'b>,
+    pub mouse: (i32, i32),
+    // where the left button went down while it is held
+    pub origin: Option<(i32, i32)>,
+    // the left button went down this frame
+    pub clicked: bool,
+}
+
+impl<'a, 'b> Ui<'a, 'b> {
+    pub fn new(canvas: &'a mut Canvas<Window>, font: &'a Glyphs<'b>, input: &InputState) -> Ui<'a, 'b> {
+        let left = input.button(MouseButton::Left);
+        Ui {
+            canvas,
+            font,
+            mouse: input.mouse,
+            origin: left.origin.filter(|_| left.down),
+            clicked: left.pressed,
+        }
+    }
+
+    // a widget only shows as pressed when the press started on it
+    pub fn state(&self, area: Rect) -> State {
+        if !area.contains_point(self.mouse) {
+            State::Idle
+        } else if self.origin.is_some_and(|origin| area.contains_point(origin)) {
+            State::Pressed
+        } else {
+            State::Hovered
+        }
+    }
+
+    pub fn clicked(&self, area: Rect) -> bool {
+        self.clicked && area.contains_point(self.mouse)
+    }
+
+    pub fn text(&mut self, text: &str, x: i32, y: i32, color: Color) {
+        self.font.draw(self.canvas, text, x, y, color);
+    }
+
+    // background and outline shared by the clickable widgets, `active` marks a
+    // selected or switched on widget
+    fn frame(&mut self, area: Rect, active: bool) -> State {
+        let state = self.state(area);
+        if state == State::Pressed {
+            self.canvas.set_draw_color(PRESSED_COLOR);
+            self.canvas.fill_rect(area).expect("Failed to draw widget");
+        }
+        let outline = match state {
+            State::Idle if active => Some(ACTIVE_COLOR),
+            State::Idle => None,
+            _ => Some(HOVER_COLOR),
+        };
+        if let Some(color) = outline {
+            self.canvas.set_draw_color(color);
+            self.canvas.draw_rect(area).expect("Failed to draw widget outline");
+        }
+        state
+    }
+}
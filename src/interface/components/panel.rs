@@ -0,0 +1,22 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+use super::Ui;
+
+// filled box that other widgets are drawn on top of
+pub struct Panel {
+    area: Rect,
+}
+
+impl Panel {
+    pub fn new(area: Rect) -> Panel {
+        Panel { area }
+    }
+
+    pub fn show(&self, ui: &mut Ui) {
+        ui.canvas.set_draw_color(Color::RGB(20, 20, 20));
+        ui.canvas.fill_rect(self.area).expect("Failed to draw panel");
+        ui.canvas.set_draw_color(Color::RGB(255, 255, 255));
+        ui.canvas.draw_rect(self.area).expect("Failed to draw panel");
+    }
+}
@@ -0,0 +1,44 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+use super::{State, Ui, TEXT_COLOR};
+
+const KNOB_WIDTH: i32 = 3;
+
+// picks a whole number between `min` and `max` by clicking or dragging along it
+pub struct Slider<'t> {
+    area: Rect,
+    text: &'t str,
+    value: i32,
+    min: i32,
+    max: i32,
+}
+
+impl<'t> Slider<'t> {
+    pub fn new(area: Rect, text: &'t str, value: i32, min: i32, max: i32) -> Slider<'t> {
+        Slider { area, text, value, min, max }
+    }
+
+    // draw the slider, returns the new value while it is being dragged
+    pub fn show(&self, ui: &mut Ui) -> Option<i32> {
+        let state = ui.frame(self.area, false);
+        let range = (self.max - self.min).max(1);
+        let track = (self.area.width() as i32 - KNOB_WIDTH).max(1);
+
+        let value = (state == State::Pressed).then(|| {
+            let offset = (ui.mouse.0 - self.area.x()).clamp(0, track);
+            // round to the nearest step
+            self.min + (offset * range + track / 2) / track
+        });
+
+        let shown = value.unwrap_or(self.value).clamp(self.min, self.max);
+        let knob_x = self.area.x() + (shown - self.min) * track / range;
+        ui.canvas.set_draw_color(Color::RGB(100, 100, 100));
+        ui.canvas
+            .fill_rect(Rect::new(knob_x, self.area.y() + 1, KNOB_WIDTH as u32, self.area.height().saturating_sub(2)))
+            .expect("Failed to draw slider");
+        ui.text(self.text, self.area.x() + KNOB_WIDTH + 1, self.area.y() + 1, TEXT_COLOR);
+
+        value.filter(|value| *value != self.value)
+    }
+}
@@ -0,0 +1,23 @@
+use sdl2::rect::Rect;
+
+use super::{Ui, TEXT_COLOR};
+
+// a button that stays outlined while it is switched on
+pub struct Toggle<'t> {
+    area: Rect,
+    text: &'t str,
+    on: bool,
+}
+
+impl<'t> Toggle<'t> {
+    pub fn new(area: Rect, text: &'t str, on: bool) -> Toggle<'t> {
+        Toggle { area, text, on }
+    }
+
+    // draw the toggle, returns the new value when it was clicked this frame
+    pub fn show(&self, ui: &mut Ui) -> Option<bool> {
+        ui.frame(self.area, self.on);
+        ui.text(self.text, self.area.x() + 2, self.area.y() + 1, TEXT_COLOR);
+        ui.clicked(self.area).then_some(!self.on)
+    }
+}
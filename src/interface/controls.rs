@@ -0,0 +1,470 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdl2::keyboard::{KeyboardState, Keycode, Mod, Scancode};
+use sdl2::mouse::MouseButton;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+use super::components::label::Label;
+use super::components::panel::Panel;
+use super::components::Ui;
+use super::{CTRL_MOD, SHIFT_MOD};
+
+const COMMAND_COUNT: usize = 29;
+const LINE_HEIGHT: i32 = 9;
+const HELP_WIDTH: u32 = 220;
+// where the binding column starts in the help overlay
+const BINDING_COLUMN: i32 = 90;
+
+// everything a key, mouse button or the wheel can be bound to
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Command {
+    Pause,
+    Step,
+    StepBack,
+    Clear,
+    Undo,
+    Redo,
+    Slower,
+    Faster,
+    NextTool,
+    NextMode,
+    ZoomIn,
+    ZoomOut,
+    Inspector,
+    Stats,
+    Search,
+    Help,
+    Quit,
+    // these act for as long as they are held
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    Minimap,
+    Rewind,
+    // these take a mouse button
+    Paint,
+    Erase,
+    Drag,
+    Pick,
+    // the wheel scrolls these
+    BrushSize,
+    Zoom,
+}
+
+impl Command {
+    pub fn all() -> [Command; COMMAND_COUNT] {
+        [
+            Command::Pause,
+            Command::Step,
+            Command::StepBack,
+            Command::Clear,
+            Command::Undo,
+            Command::Redo,
+            Command::Slower,
+            Command::Faster,
+            Command::NextTool,
+            Command::NextMode,
+            Command::ZoomIn,
+            Command::ZoomOut,
+            Command::Inspector,
+            Command::Stats,
+            Command::Search,
+            Command::Help,
+            Command::Quit,
+            Command::PanLeft,
+            Command::PanRight,
+            Command::PanUp,
+            Command::PanDown,
+            Command::Minimap,
+            Command::Rewind,
+            Command::Paint,
+            Command::Erase,
+            Command::Drag,
+            Command::Pick,
+            Command::BrushSize,
+            Command::Zoom,
+        ]
+    }
+
+    // key used for the command in the controls file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Pause => "pause",
+            Command::Step => "step",
+            Command::StepBack => "step_back",
+            Command::Clear => "clear",
+            Command::Undo => "undo",
+            Command::Redo => "redo",
+            Command::Slower => "slower",
+            Command::Faster => "faster",
+            Command::NextTool => "next_tool",
+            Command::NextMode => "next_mode",
+            Command::ZoomIn => "zoom_in",
+            Command::ZoomOut => "zoom_out",
+            Command::Inspector => "inspector",
+            Command::Stats => "stats",
+            Command::Search => "search",
+            Command::Help => "help",
+            Command::Quit => "quit",
+            Command::PanLeft => "pan_left",
+            Command::PanRight => "pan_right",
+            Command::PanUp => "pan_up",
+            Command::PanDown => "pan_down",
+            Command::Minimap => "minimap",
+            Command::Rewind => "rewind",
+            Command::Paint => "paint",
+            Command::Erase => "erase",
+            Command::Drag => "drag",
+            Command::Pick => "pick",
+            Command::BrushSize => "brush_size",
+            Command::Zoom => "zoom",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Command::Pause => "pause",
+            Command::Step => "step a tick",
+            Command::StepBack => "step back a tick",
+            Command::Clear => "clear the world",
+            Command::Undo => "undo",
+            Command::Redo => "redo",
+            Command::Slower => "slow down",
+            Command::Faster => "speed up",
+            Command::NextTool => "next tool",
+            Command::NextMode => "next mode",
+            Command::ZoomIn => "zoom in",
+            Command::ZoomOut => "zoom out",
+            Command::Inspector => "cell inspector",
+            Command::Stats => "statistics",
+            Command::Search => "search species",
+            Command::Help => "this help",
+            Command::Quit => "quit",
+            Command::PanLeft => "pan left",
+            Command::PanRight => "pan right",
+            Command::PanUp => "pan up",
+            Command::PanDown => "pan down",
+            Command::Minimap => "show minimap",
+            Command::Rewind => "rewind",
+            Command::Paint => "paint",
+            Command::Erase => "erase",
+            Command::Drag => "drag the view",
+            Command::Pick => "pick species",
+            Command::BrushSize => "brush size",
+            Command::Zoom => "zoom",
+        }
+    }
+
+    // whether the command can be driven by the input, painting and picking need
+    // a mouse button, the brush size and zoom the wheel and the rest a key
+    fn accepts(&self, input: Input) -> bool {
+        match self {
+            Command::Paint | Command::Erase | Command::Drag | Command::Pick => matches!(input, Input::Mouse(_)),
+            Command::BrushSize | Command::Zoom => input == Input::Wheel,
+            _ => matches!(input, Input::Key(_)),
+        }
+    }
+}
+
+// `text` after `prefix`, ignoring ascii case. safe on any text, unlike slicing
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&text[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+// the quoted strings of a value that is either a single quoted string or a
+// list of them, optionally followed by a comment
+fn parse_strings(value: &str) -> Result<Vec<&str>, String> {
+    let mut rest = value.trim_start();
+    let list = rest.starts_with('[');
+    if list {
+        rest = rest[1..].trim_start();
+    }
+
+    let mut strings = Vec::new();
+    loop {
+        if list && rest.starts_with(']') {
+            rest = &rest[1..];
+            break;
+        }
+        let Some(quoted) = rest.strip_prefix('"') else {
+            return Err(format!("expected a quoted binding, found `{}`", rest));
+        };
+        let end = quoted.find('"').ok_or_else(|| "missing closing quote".to_string())?;
+        strings.push(&quoted[..end]);
+        rest = quoted[end + 1..].trim_start();
+        if !list {
+            break;
+        }
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(']') {
+            return Err(format!("expected `,` or `]`, found `{}`", rest));
+        }
+    }
+
+    let rest = rest.trim();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(format!("unexpected `{}` after the binding", rest));
+    }
+    Ok(strings)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Input {
+    Key(Keycode),
+    Mouse(MouseButton),
+    Wheel,
+}
+
+// an input together with the modifiers that have to be held with it.
+// modifiers are ignored for mouse buttons, shift and ctrl change what a
+// stroke does instead
+#[derive(Clone, Copy, PartialEq)]
+pub struct Binding {
+    pub input: Input,
+    pub ctrl: bool,
+    pub shift: bool,
+}
+
+impl Binding {
+    // parse a binding such as `Space`, `Ctrl+Shift+Z`, `MouseLeft` or `Ctrl+Wheel`.
+    // key names are the ones SDL uses
+    pub fn parse(text: &str) -> Option<Binding> {
+        let mut rest = text.trim();
+        let mut ctrl = false;
+        let mut shift = false;
+        loop {
+            if let Some(after) = strip_prefix_ignore_case(rest, "ctrl+") {
+                ctrl = true;
+                rest = after;
+            } else if let Some(after) = strip_prefix_ignore_case(rest, "shift+") {
+                shift = true;
+                rest = after;
+            } else {
+                break;
+            }
+        }
+
+        let input = match rest.to_lowercase().as_str() {
+            "wheel" => Input::Wheel,
+            "mouseleft" => Input::Mouse(MouseButton::Left),
+            "mousemiddle" => Input::Mouse(MouseButton::Middle),
+            "mouseright" => Input::Mouse(MouseButton::Right),
+            "mousex1" => Input::Mouse(MouseButton::X1),
+            "mousex2" => Input::Mouse(MouseButton::X2),
+            _ => Input::Key(Keycode::from_name(rest)?),
+        };
+        Some(Binding { input, ctrl, shift })
+    }
+
+    fn name(&self) -> String {
+        let input = match self.input {
+            Input::Key(keycode) => keycode.name(),
+            Input::Mouse(button) => format!("Mouse{:?}", button),
+            Input::Wheel => "Wheel".to_string(),
+        };
+        let ctrl = if self.ctrl { "Ctrl+" } else { "" };
+        let shift = if self.shift { "Shift+" } else { "" };
+        format!("{}{}{}", ctrl, shift, input)
+    }
+
+    fn modifiers_match(&self, ctrl: bool, shift: bool) -> bool {
+        self.ctrl == ctrl && self.shift == shift
+    }
+}
+
+// the bindings of every command, loaded from the controls file on top of the defaults
+pub struct Controls {
+    bindings: Vec<(Command, Binding)>,
+}
+
+impl Controls {
+    pub fn new() -> Controls {
+        let defaults = [
+            (Command::Pause, "Space"),
+            (Command::Step, "."),
+            (Command::StepBack, ","),
+            (Command::Clear, "C"),
+            (Command::Undo, "Ctrl+Z"),
+            (Command::Redo, "Ctrl+Y"),
+            (Command::Redo, "Ctrl+Shift+Z"),
+            (Command::Slower, "["),
+            (Command::Faster, "]"),
+            (Command::NextTool, "T"),
+            (Command::NextMode, "M"),
+            (Command::ZoomIn, "="),
+            (Command::ZoomIn, "Keypad +"),
+            (Command::ZoomOut, "-"),
+            (Command::ZoomOut, "Keypad -"),
+            (Command::Inspector, "I"),
+            (Command::Stats, "F3"),
+            (Command::Search, "/"),
+            (Command::Help, "F1"),
+            (Command::Quit, "Escape"),
+            (Command::PanLeft, "A"),
+            (Command::PanRight, "D"),
+            (Command::PanUp, "W"),
+            (Command::PanDown, "S"),
+            (Command::Minimap, "Z"),
+            (Command::Rewind, "R"),
+            (Command::Paint, "MouseLeft"),
+            (Command::Erase, "MouseRight"),
+            (Command::Drag, "MouseMiddle"),
+            // clicking without moving picks, moving drags
+            (Command::Pick, "MouseMiddle"),
+            (Command::BrushSize, "Wheel"),
+            (Command::Zoom, "Ctrl+Wheel"),
+        ];
+        Controls {
+            bindings: defaults
+                .into_iter()
+                .map(|(command, text)| (command, Binding::parse(text).expect("Failed to parse default binding")))
+                .collect(),
+        }
+    }
+
+    // $XDG_CONFIG_HOME/crumb/controls.toml, falling back to ~/.config
+    pub fn default_path() -> Option<PathBuf> {
+        let config = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config.join("crumb").join("controls.toml"))
+    }
+
+    // read a controls file where every line binds a command to one or more inputs, e.g.
+    //
+    //     # pause with p instead of space
+    //     pause = "P"
+    //     redo = ["Ctrl+Y", "Ctrl+Shift+Z"]
+    //     zoom = "Wheel"
+    //     brush_size = "Ctrl+Wheel"
+    //     step_back = [","]  # quotes keep the comma out of the list
+    //
+    // commands left out keep their default bindings, an empty list unbinds one
+    pub fn load(path: &Path) -> Result<Controls, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read controls {}: {}", path.display(), e))?;
+
+        let mut controls = Controls::new();
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| format!("{}:{}: {}", path.display(), line_no + 1, message);
+
+            let Some((name, value)) = line.split_once('=') else {
+                return Err(error("expected `<command> = \"<binding>\"`".to_string()));
+            };
+            let name = name.trim();
+            let command = Command::all()
+                .into_iter()
+                .find(|command| command.name() == name)
+                .ok_or_else(|| error(format!("unknown command `{}`", name)))?;
+
+            let mut bindings = Vec::new();
+            for text in parse_strings(value).map_err(error)? {
+                let binding = Binding::parse(text).ok_or_else(|| error(format!("unknown key `{}`", text)))?;
+                if !command.accepts(binding.input) {
+                    return Err(error(format!("`{}` cannot be bound to `{}`", name, text)));
+                }
+                // mouse buttons are matched whatever modifiers are held
+                if matches!(binding.input, Input::Mouse(_)) && (binding.ctrl || binding.shift) {
+                    return Err(error(format!("mouse buttons cannot take modifiers, `{}`", text)));
+                }
+                bindings.push((command, binding));
+            }
+
+            controls.bindings.retain(|(c, _)| *c != command);
+            controls.bindings.extend(bindings);
+        }
+        Ok(controls)
+    }
+
+    // the command bound to a key that was just pressed
+    pub fn pressed(&self, keycode: Keycode, keymod: Mod) -> Option<Command> {
+        let ctrl = keymod.intersects(CTRL_MOD);
+        let shift = keymod.intersects(SHIFT_MOD);
+        self.bindings
+            .iter()
+            .find(|(_, binding)| binding.input == Input::Key(keycode) && binding.modifiers_match(ctrl, shift))
+            .map(|(command, _)| *command)
+    }
+
+    // whether a key bound to the command is held down
+    pub fn held(&self, command: Command, keyboard: &KeyboardState, keymod: Mod) -> bool {
+        let ctrl = keymod.intersects(CTRL_MOD);
+        let shift = keymod.intersects(SHIFT_MOD);
+        self.bindings
+            .iter()
+            .filter(|(c, _)| *c == command)
+            .any(|(_, binding)| match binding.input {
+                Input::Key(keycode) => {
+                    binding.modifiers_match(ctrl, shift)
+                        && Scancode::from_keycode(keycode).is_some_and(|scancode| keyboard.is_scancode_pressed(scancode))
+                }
+                Input::Mouse(_) | Input::Wheel => false,
+            })
+    }
+
+    // the first mouse button bound to the command, Unknown when there is none
+    pub fn button(&self, command: Command) -> MouseButton {
+        self.bindings
+            .iter()
+            .find_map(|(c, binding)| match binding.input {
+                Input::Mouse(button) if *c == command => Some(button),
+                _ => None,
+            })
+            .unwrap_or(MouseButton::Unknown)
+    }
+
+    // the command the wheel drives with the given modifiers held
+    pub fn wheel(&self, keymod: Mod) -> Option<Command> {
+        let ctrl = keymod.intersects(CTRL_MOD);
+        let shift = keymod.intersects(SHIFT_MOD);
+        self.bindings
+            .iter()
+            .find(|(_, binding)| binding.input == Input::Wheel && binding.modifiers_match(ctrl, shift))
+            .map(|(command, _)| *command)
+    }
+
+    fn describe(&self, command: Command) -> String {
+        let names: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|(c, _)| *c == command)
+            .map(|(_, binding)| binding.name())
+            .collect();
+        if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.join(", ")
+        }
+    }
+
+    // list every command with its bindings in a box centred in `area`
+    pub fn draw_help(&self, ui: &mut Ui, area: Rect) {
+        let commands = Command::all();
+        let height = (commands.len() as i32 + 2) * LINE_HEIGHT + 4;
+        let panel = Rect::new(area.center().x() - HELP_WIDTH as i32 / 2, area.center().y() - height / 2, HELP_WIDTH, height as u32);
+        Panel::new(panel).show(ui);
+
+        let x = panel.x() + 3;
+        let mut y = panel.y() + 2;
+        Label::new(x, y, "CONTROLS").color(Color::RGB(255, 255, 255)).show(ui);
+        y += LINE_HEIGHT * 2;
+        for command in commands {
+            Label::new(x, y, command.description()).show(ui);
+            Label::new(x + BINDING_COLUMN, y, self.describe(command).as_str()).show(ui);
+            y += LINE_HEIGHT;
+        }
+    }
+}
@@ -1,4 +1,12 @@
+// initial window size, the window can be resized afterwards
 pub static WIDTH: usize = 1100;
 pub static HEIGHT: usize = 800;
 pub static UI_X: usize = 30; // 30 px to the right for the UI
-pub static UI_Y: usize = 20; // 30 px down for the UI
+pub static UI_Y: usize = 32; // 32 px down for the palette
+
+// logical pixel size on a standard density display
+pub static BASE_SCALE: f32 = 2.0;
+pub static BASE_DPI: f32 = 96.0;
+
+// where the toolbar saves the world to and loads it from
+pub static SAVE_PATH: &str = "world.crumb";
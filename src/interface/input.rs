@@ -0,0 +1,152 @@
+use sdl2::event::Event;
+use sdl2::keyboard::Mod;
+use sdl2::mouse::{MouseButton, MouseState};
+
+use super::layout::Layout;
+
+const BUTTON_COUNT: usize = 5;
+// a release further than this from the press, in logical pixels, ends a drag rather than a click
+const CLICK_SLOP: i32 = 2;
+
+#[derive(Clone, Copy, Default)]
+pub struct ButtonState {
+    pub down: bool,
+    // went down or up at some point during this frame
+    pub pressed: bool,
+    pub released: bool,
+    // where the button last went down, kept until the next press
+    pub origin: Option<(i32, i32)>,
+    // where it last came up
+    release: Option<(i32, i32)>,
+}
+
+// a modifier key, both the left and right keys count
+#[derive(Clone, Copy, Default)]
+pub struct ModifierState {
+    pub down: bool,
+    pub pressed: bool,
+    pub released: bool,
+}
+
+impl ModifierState {
+    fn update(&mut self, down: bool) {
+        self.pressed = down && !self.down;
+        self.released = !down && self.down;
+        self.down = down;
+    }
+}
+
+// mouse buttons, modifiers and the wheel for the current frame, gathered from
+// the events so that presses and releases shorter than a frame are not lost
+pub struct InputState {
+    // cursor in logical pixels
+    pub mouse: (i32, i32),
+    buttons: [ButtonState; BUTTON_COUNT],
+    pub ctrl: ModifierState,
+    pub shift: ModifierState,
+    pub alt: ModifierState,
+    // wheel steps scrolled this frame, positive away from the user
+    pub wheel: i32,
+}
+
+fn index(button: MouseButton) -> Option<usize> {
+    match button {
+        MouseButton::Left => Some(0),
+        MouseButton::Middle => Some(1),
+        MouseButton::Right => Some(2),
+        MouseButton::X1 => Some(3),
+        MouseButton::X2 => Some(4),
+        MouseButton::Unknown => None,
+    }
+}
+
+impl InputState {
+    pub fn new() -> InputState {
+        InputState {
+            mouse: (0, 0),
+            buttons: [ButtonState::default(); BUTTON_COUNT],
+            ctrl: ModifierState::default(),
+            shift: ModifierState::default(),
+            alt: ModifierState::default(),
+            wheel: 0,
+        }
+    }
+
+    // forget what happened last frame, call before polling events
+    pub fn begin_frame(&mut self) {
+        for button in self.buttons.iter_mut() {
+            button.pressed = false;
+            button.released = false;
+        }
+        self.wheel = 0;
+    }
+
+    pub fn handle(&mut self, event: &Event, layout: &Layout) {
+        match *event {
+            Event::MouseButtonDown { mouse_btn, x, y, .. } => {
+                if let Some(i) = index(mouse_btn) {
+                    let button = &mut self.buttons[i];
+                    button.down = true;
+                    button.pressed = true;
+                    button.origin = Some(layout.to_logical(x, y));
+                }
+            }
+            Event::MouseButtonUp { mouse_btn, x, y, .. } => {
+                if let Some(i) = index(mouse_btn) {
+                    let button = &mut self.buttons[i];
+                    button.down = false;
+                    button.released = true;
+                    button.release = Some(layout.to_logical(x, y));
+                }
+            }
+            Event::MouseWheel { y, .. } => self.wheel += y,
+            _ => {}
+        }
+    }
+
+    // catch up with the state of the devices after the events were handled.
+    // buttons released while the window had no focus are let go here
+    pub fn end_frame(&mut self, mouse: &MouseState, keymod: Mod, layout: &Layout) {
+        self.mouse = layout.to_logical(mouse.x(), mouse.y());
+        for button in [MouseButton::Left, MouseButton::Middle, MouseButton::Right, MouseButton::X1, MouseButton::X2] {
+            let state = &mut self.buttons[index(button).expect("known button")];
+            if state.down && !mouse.is_mouse_button_pressed(button) {
+                state.down = false;
+                state.released = true;
+                state.release = Some(self.mouse);
+            }
+        }
+        self.ctrl.update(keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD));
+        self.shift.update(keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD));
+        self.alt.update(keymod.intersects(Mod::LALTMOD | Mod::RALTMOD));
+    }
+
+    pub fn button(&self, button: MouseButton) -> ButtonState {
+        index(button).map(|i| self.buttons[i]).unwrap_or_default()
+    }
+
+    pub fn down(&self, button: MouseButton) -> bool {
+        self.button(button).down
+    }
+
+    pub fn pressed(&self, button: MouseButton) -> bool {
+        self.button(button).pressed
+    }
+
+    // released this frame close to where it went down
+    pub fn clicked(&self, button: MouseButton) -> bool {
+        let state = self.button(button);
+        match (state.released, state.origin, state.release) {
+            (true, Some((ox, oy)), Some((rx, ry))) => (rx - ox).abs() <= CLICK_SLOP && (ry - oy).abs() <= CLICK_SLOP,
+            _ => false,
+        }
+    }
+
+    // where the drag started while the button is held and the mouse has moved away from it
+    pub fn drag_origin(&self, button: MouseButton) -> Option<(i32, i32)> {
+        let state = self.button(button);
+        let (ox, oy) = state.origin.filter(|_| state.down)?;
+        let (mx, my) = self.mouse;
+        ((mx - ox).abs() > CLICK_SLOP || (my - oy).abs() > CLICK_SLOP).then_some((ox, oy))
+    }
+}
@@ -0,0 +1,140 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::engine::history::History;
+use crate::engine::species::Species;
+use crate::engine::{Cell, World};
+
+use super::components::glyphs::Glyphs;
+use super::draw_text;
+
+const LINE_HEIGHT: i32 = 9;
+const LINES: i32 = 7;
+const PANEL_WIDTH: u32 = 110;
+// first line of each editable field, the others are read only
+const SPECIES_LINE: i32 = 1;
+const RA_LINE: i32 = 2;
+const RB_LINE: i32 = 3;
+const CLOCK_LINE: i32 = 4;
+
+// shows every field of the cell under the cursor. while the simulation is
+// paused a cell can be pinned and its fields changed with the mouse wheel
+pub struct Inspector {
+    pub open: bool,
+    pinned: Option<(i32, i32)>,
+    area: Rect,
+}
+
+impl Inspector {
+    pub fn new(x: i32, y: i32) -> Inspector {
+        Inspector {
+            open: false,
+            pinned: None,
+            area: Rect::new(x, y, PANEL_WIDTH, (LINES * LINE_HEIGHT + 4) as u32),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.pinned = None;
+    }
+
+    // pin the cell at (x, y), pinning it again releases it
+    pub fn pin(&mut self, x: i32, y: i32) {
+        self.pinned = if self.pinned == Some((x, y)) { None } else { Some((x, y)) };
+    }
+
+    pub fn unpin(&mut self) {
+        self.pinned = None;
+    }
+
+    pub fn contains(&self, mouse_coords: (i32, i32)) -> bool {
+        self.open && self.area.contains_point(mouse_coords)
+    }
+
+    // the pinned cell, or the one under the cursor
+    fn target(&self, hovered: (i32, i32)) -> (i32, i32) {
+        self.pinned.unwrap_or(hovered)
+    }
+
+    fn line_at(&self, mouse_coords: (i32, i32)) -> i32 {
+        (mouse_coords.1 - self.area.y() - 2) / LINE_HEIGHT
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas<Window>, font: &Glyphs, world: &World, hovered: (i32, i32), mouse_coords: (i32, i32)) {
+        if !self.open {
+            return;
+        }
+        let (x, y) = self.target(hovered);
+
+        canvas.set_draw_color(Color::RGB(20, 20, 20));
+        canvas.fill_rect(self.area).expect("Failed to draw inspector");
+        canvas.set_draw_color(if self.pinned.is_some() { Color::RGB(255, 0, 0) } else { Color::RGB(255, 255, 255) });
+        canvas.draw_rect(self.area).expect("Failed to draw inspector");
+
+        let lines = if x < 0 || x > world.width() - 1 || y < 0 || y > world.height() - 1 {
+            vec![format!("X {} Y {}", x, y), "out of bounds".to_string()]
+        } else {
+            let cell = world.get(x as usize, y as usize);
+            let wind = world.get_wind(x, y);
+            vec![
+                format!("X {} Y {}", x, y),
+                format!("species {:?}", cell.species),
+                format!("ra {}", cell.ra),
+                format!("rb {}", cell.rb),
+                format!("clock {}", cell.clock),
+                format!("wind dx {} dy {}", wind.dx, wind.dy),
+                format!("pres {} dens {}", wind.pressure, wind.density),
+            ]
+        };
+
+        // underline the field the wheel would change
+        if self.pinned.is_some() && self.area.contains_point(mouse_coords) {
+            let line = self.line_at(mouse_coords);
+            if (SPECIES_LINE..=CLOCK_LINE).contains(&line) {
+                canvas.set_draw_color(Color::RGB(255, 0, 0));
+                canvas
+                    .draw_rect(Rect::new(self.area.x() + 1, self.area.y() + 2 + line * LINE_HEIGHT, PANEL_WIDTH - 2, LINE_HEIGHT as u32))
+                    .expect("Failed to draw inspector field");
+            }
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            draw_text(canvas, font, line, self.area.x() + 3, self.area.y() + 2 + i as i32 * LINE_HEIGHT);
+        }
+    }
+
+    // change the field of the pinned cell under the mouse, returns whether the scroll was used
+    pub fn scroll(&mut self, world: &mut World, history: &mut History, mouse_coords: (i32, i32), delta: i32) -> bool {
+        if !self.contains(mouse_coords) {
+            return false;
+        }
+        let Some((x, y)) = self.pinned else {
+            return true;
+        };
+        if x < 0 || x > world.width() - 1 || y < 0 || y > world.height() - 1 {
+            return true;
+        }
+
+        let cell = world.get(x as usize, y as usize);
+        let edited = match self.line_at(mouse_coords) {
+            SPECIES_LINE => {
+                let all = Species::all();
+                let i = all.iter().position(|species| *species == cell.species).unwrap_or(0) as i32;
+                let species = all[(i + delta).rem_euclid(all.len() as i32) as usize];
+                Cell { species, ..cell }
+            }
+            RA_LINE => Cell { ra: cell.ra.wrapping_add(delta as u8), ..cell },
+            RB_LINE => Cell { rb: cell.rb.wrapping_add(delta as u8), ..cell },
+            CLOCK_LINE => Cell { clock: cell.clock.wrapping_add(delta as u8), ..cell },
+            _ => return true,
+        };
+
+        history.begin();
+        history.set(world, x as usize, y as usize, edited);
+        history.commit();
+        true
+    }
+}
@@ -0,0 +1,63 @@
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use super::defaults::{BASE_DPI, BASE_SCALE, UI_X, UI_Y};
+
+// positions of the different parts of the screen in logical (scaled) pixels
+pub struct Layout {
+    // logical pixels per drawable pixel
+    pub scale: f32,
+    // drawable pixels per window coordinate, above 1 on HiDPI displays
+    pub pixel_ratio: f32,
+    pub height: i32,
+    // the world viewport
+    pub view: Rect,
+    // species palette along the bottom
+    pub palette: Rect,
+    // side bar to the right of the viewport
+    pub sidebar: Rect,
+}
+
+impl Layout {
+    // `scale` replaces the logical pixel size picked from the display density
+    pub fn new(canvas: &Canvas<Window>, scale: Option<f32>) -> Layout {
+        let window = canvas.window();
+        let (window_width, _) = window.size();
+        let (drawable_width, drawable_height) = canvas.output_size().expect("Failed to get drawable size");
+        let pixel_ratio = drawable_width as f32 / window_width.max(1) as f32;
+
+        // platforms that hand us a larger drawable already account for the dpi,
+        // everywhere else the reported dpi decides how large a logical pixel is
+        let dpi = window
+            .display_index()
+            .and_then(|index| window.subsystem().display_dpi(index))
+            .ok()
+            .map(|(_, hdpi, _)| hdpi);
+        let dpi_factor = dpi.map(|dpi| (dpi / BASE_DPI).round()).unwrap_or(1.0).max(1.0);
+        let scale = match scale {
+            Some(scale) => scale * pixel_ratio,
+            None => BASE_SCALE * pixel_ratio.max(dpi_factor),
+        };
+
+        let width = (drawable_width as f32 / scale) as i32;
+        let height = (drawable_height as f32 / scale) as i32;
+        let view_width = (width - UI_X as i32).max(1);
+        let view_height = (height - UI_Y as i32).max(1);
+
+        Layout {
+            scale,
+            pixel_ratio,
+            height,
+            view: Rect::new(0, 0, view_width as u32, view_height as u32),
+            palette: Rect::new(0, view_height, view_width as u32, UI_Y as u32),
+            sidebar: Rect::new(view_width, 0, UI_X as u32, height.max(1) as u32),
+        }
+    }
+
+    // convert window coordinates, as reported by mouse events, into logical pixels
+    pub fn to_logical(&self, x: i32, y: i32) -> (i32, i32) {
+        let factor = self.pixel_ratio / self.scale;
+        ((x as f32 * factor) as i32, (y as f32 * factor) as i32)
+    }
+}
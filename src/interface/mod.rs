@@ -1,23 +1,55 @@
-use crate::engine::species::SPECIES_COUNT;
-use crate::engine::{Engine, species, Cell, World};
+use crate::engine::{Engine, species, World};
 
-use sdl2::rect::Point;
+use sdl2::keyboard::Mod;
+use sdl2::mouse::MouseButton;
 use sdl2::render::Texture;
 use sdl2::{pixels::PixelFormatEnum};
 use sdl2::{pixels::Color, rect::Rect, render::Canvas};
 
 use species::Species;
+pub mod brush;
+pub mod camera;
+pub mod clock;
+pub mod controls;
 pub mod defaults;
+pub mod input;
+pub mod inspector;
+pub mod layout;
+pub mod palette;
+pub mod stats;
+pub mod toolbar;
 pub mod utils;
 mod components;
 use rand::Rng;
-
+use std::path::Path;
+
+use self::brush::{Brush, Mode, Tool};
+use self::camera::Camera;
+use self::clock::SimClock;
+use self::controls::{Command, Controls};
+use self::components::button::Button;
+use self::components::glyphs::Glyphs;
+use self::components::label::Label;
+use self::components::toggle::Toggle;
+use self::components::{Ui, TEXT_COLOR};
+use self::input::InputState;
+use self::inspector::Inspector;
+use self::palette::Palette;
+use self::stats::Stats;
+use self::toolbar::{toolbar, Action};
+use self::layout::Layout;
 use self::utils::cell_to_color;
-use self::{defaults::{UI_X, UI_Y, WIDTH, HEIGHT}};
 
 const BACKGROUND_COLOR: Color = Color::RGB(0, 0, 0);
-const MAX_CURSOR_SIZE: usize = 300;
 const ZOOM: i32 = 100;
+const PAN_SPEED: i32 = 8;
+// tool buttons in the side bar, below the selected species
+const TOOL_SLOT_TOP: i32 = 14;
+const TOOL_SLOT_HEIGHT: i32 = 12;
+// simulation toolbar, below the placement mode
+const TOOLBAR_TOP: i32 = TOOL_SLOT_TOP + (brush::TOOL_COUNT as i32 + 3) * TOOL_SLOT_HEIGHT;
+const CTRL_MOD: Mod = Mod::LCTRLMOD.union(Mod::RCTRLMOD);
+const SHIFT_MOD: Mod = Mod::LSHIFTMOD.union(Mod::RSHIFTMOD);
 const FONT: &[u8] = include_bytes!("res/Monocraft.ttf");
 
 pub fn vary_color(color: Color) -> Color {
@@ -40,29 +72,51 @@ pub fn vary_color(color: Color) -> Color {
     Color::RGB(r, g, b)
 }
 
-fn is_in_window(x: i32, y: i32, wx: i32, wy: i32) -> bool {
-    x >= 0 && x < wx as i32 && y >= 0 && y < wy as i32
+// settings for the window and the simulation loop, from the command line
+#[derive(Default)]
+pub struct Options {
+    // logical pixel size, otherwise picked from the display density
+    pub scale: Option<f32>,
+    pub paused: bool,
+    // upper bound on ticks per second
+    pub max_tps: Option<f64>,
 }
+
 pub struct Interface;
 // create an array of possible cell species for our selector
 
 impl Interface {
 
     
-    pub fn run(mut engine_: Engine) {
-        let cell_species: &[Species] = &Species::all();
-
-        let mut selected_index: usize = 2;
-        let mut cursor_size:i32 = 3;
-
-        let mut paused: bool = false;
-        let mut ctrl_pressed: bool = false;
-        let mut mouse_left_clicked: bool = false;
+    pub fn run(mut engine_: Engine, options: Options) {
+
+        let mut palette = Palette::new();
+        let mut brush = Brush::new();
+        let mut input = InputState::new();
+        let mut help_open = false;
+        // the controls file is optional, the defaults are used without it
+        let controls = match Controls::default_path().filter(|path| path.exists()) {
+            Some(path) => Controls::load(&path).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                Controls::new()
+            }),
+            None => Controls::new(),
+        };
+        let paint_button = controls.button(Command::Paint);
+        let erase_button = controls.button(Command::Erase);
+        let drag_button = controls.button(Command::Drag);
+        let pick_button = controls.button(Command::Pick);
+
+        let mut paused: bool = options.paused;
+        let mut clock = SimClock::new(options.max_tps);
+        // below the status line
+        let mut inspector = Inspector::new(0, 10);
+        let mut stats = Stats::new();
         
         // read font data and use in ttf_context
         let ttf_context = sdl2::ttf::init().unwrap();
-        let mut font = ttf_context.load_font_from_rwops(sdl2::rwops::RWops::from_bytes(FONT).unwrap(), 8).unwrap();
-        font.set_style(sdl2::ttf::FontStyle::BOLD);
+        let mut ttf_font = ttf_context.load_font_from_rwops(sdl2::rwops::RWops::from_bytes(FONT).unwrap(), 8).unwrap();
+        ttf_font.set_style(sdl2::ttf::FontStyle::BOLD);
 
         let sdl = sdl2::init().expect("Failed to initialize SDL2");
         
@@ -71,6 +125,8 @@ impl Interface {
             let window = video
                 .window("Crumb", defaults::WIDTH as u32, defaults::HEIGHT as u32)
                 .position_centered()
+                .resizable()
+                .allow_highdpi()
                 .build()
                 .expect("Failed to create window");
             window
@@ -81,389 +137,403 @@ impl Interface {
                 .expect("Failed to create canvas")
         };
         
-        // change canvas scale to 2.0
-        canvas.set_scale(2.0, 2.0).expect("Failed to set scale");
-        let viewport = canvas.viewport();
-        let vwidth = viewport.width() as u32;
-        let vheight = viewport.height() as u32;
+        // text is drawn from glyph textures rendered once and reused every frame
+        let glyph_texture_creator = canvas.texture_creator();
+        let font = Glyphs::new(ttf_font, &glyph_texture_creator);
+
+        // scale the canvas according to the display density
+        let mut layout = Layout::new(&canvas, options.scale);
+        canvas.set_scale(layout.scale, layout.scale).expect("Failed to set scale");
+
+        // the world is drawn in the area left over by the palette and the side bar
+        let mut camera = Camera::new(layout.view.width() as i32, layout.view.height() as i32);
+        let mut drag_anchor: Option<(i32, i32)> = None;
 
         let mut event_pump = sdl.event_pump().expect("Failed to create event pump");
         // start game loop
 
         // draw appropriate textures
-        let mut zoomed_texture_creator = canvas.texture_creator();
+        let zoomed_texture_creator = canvas.texture_creator();
         let mut zoomed_texture = zoomed_texture_creator
             .create_texture_streaming(PixelFormatEnum::RGB24, ZOOM as u32, ZOOM as u32)
             .unwrap();
 
         loop {
-            let start_time = std::time::Instant::now();
+            let mut zoom_delta = 0;
+            let mut step = false;
             
+            input.begin_frame();
             for event in event_pump.poll_iter() {
+                input.handle(&event, &layout);
                 match event {
-
-                    sdl2::event::Event::MouseButtonDown { mouse_btn, .. } => {
-                        if mouse_btn == sdl2::mouse::MouseButton::Left {
-                            mouse_left_clicked = true;
-                        }
-                    }
+                    // while searching the palette, keys go to the search box
                     sdl2::event::Event::KeyDown {
                         keycode: Some(keycode),
                         ..
-                    } => match keycode {
-
-                        // when spacebar is pressed, pause the simulation
-                        sdl2::keyboard::Keycode::Space => {
+                    } if palette.searching => palette.search_key(keycode),
+                    sdl2::event::Event::TextInput { text, .. } if palette.searching => palette.type_text(&text),
+                    sdl2::event::Event::KeyDown {
+                        keycode: Some(keycode),
+                        keymod,
+                        ..
+                    } => match controls.pressed(keycode, keymod) {
+                        Some(Command::Pause) => {
                             paused = !paused;
+                            // cells move once the simulation runs again
+                            inspector.unpin();
                         }
-                        sdl2::keyboard::Keycode::C => {
-                            engine_.world.clear();
+                        Some(Command::Inspector) => inspector.toggle(),
+                        Some(Command::Stats) => stats.toggle(),
+                        Some(Command::Search) => palette.start_search(),
+                        Some(Command::Help) => help_open = !help_open,
+                        Some(Command::Clear) => engine_.history.clear(&mut engine_.world),
+                        Some(Command::Undo) => engine_.history.undo(&mut engine_.world),
+                        Some(Command::Redo) => engine_.history.redo(&mut engine_.world),
+                        // step a single tick back or forward in time, stepping forward pauses
+                        Some(Command::StepBack) => engine_.step_back(),
+                        Some(Command::Step) => {
+                            paused = true;
+                            step = true;
                         }
-
-                        // when the escape key is pressed, quit the simulation
-                        sdl2::keyboard::Keycode::Escape => {
-                            return;
-                        }
-                        _ => {}
+                        Some(Command::Slower) => clock.slower(),
+                        Some(Command::Faster) => clock.faster(),
+                        Some(Command::NextTool) => brush.tool = brush.tool.next(),
+                        Some(Command::NextMode) => brush.mode = brush.mode.next(),
+                        Some(Command::ZoomIn) => zoom_delta += 1,
+                        Some(Command::ZoomOut) => zoom_delta -= 1,
+                        Some(Command::Quit) => return,
+                        Some(_) => {}
+                        // number keys pick from the species shown in the palette
+                        None => match keycode {
+                            sdl2::keyboard::Keycode::Num1 => palette.hotkey(0),
+                            sdl2::keyboard::Keycode::Num2 => palette.hotkey(1),
+                            sdl2::keyboard::Keycode::Num3 => palette.hotkey(2),
+                            sdl2::keyboard::Keycode::Num4 => palette.hotkey(3),
+                            sdl2::keyboard::Keycode::Num5 => palette.hotkey(4),
+                            sdl2::keyboard::Keycode::Num6 => palette.hotkey(5),
+                            sdl2::keyboard::Keycode::Num7 => palette.hotkey(6),
+                            sdl2::keyboard::Keycode::Num8 => palette.hotkey(7),
+                            sdl2::keyboard::Keycode::Num9 => palette.hotkey(8),
+                            sdl2::keyboard::Keycode::Num0 => palette.hotkey(9),
+                            _ => {}
+                        },
                     },
                     sdl2::event::Event::Quit { .. } => return,
-                    
-                    sdl2::event::Event::MouseWheel { y, .. } => {
-                      
-                        if y > 0 {
-                            cursor_size = (cursor_size + 1).min(MAX_CURSOR_SIZE as i32);
-                        } else {
-                            cursor_size = (cursor_size - 1).max(1);
-                        }
-                    
-                    }
-                    _ => {
-                        mouse_left_clicked = false;
+                    sdl2::event::Event::Window {
+                        win_event: sdl2::event::WindowEvent::SizeChanged(..) | sdl2::event::WindowEvent::Moved(..),
+                        ..
+                    } => {
+                        // the new size or display may need a different scale
+                        layout = Layout::new(&canvas, options.scale);
+                        canvas.set_scale(layout.scale, layout.scale).expect("Failed to set scale");
+                        camera.view_width = layout.view.width() as i32;
+                        camera.view_height = layout.view.height() as i32;
+                        camera.clamp(engine_.world.width(), engine_.world.height());
                     }
+                    
+                    _ => {}
                 }
             }
 
             let keyboard_state = event_pump.keyboard_state();
-            if keyboard_state.is_scancode_pressed(sdl2::keyboard::Scancode::LCtrl) {
-               ctrl_pressed = true;
+            let keymod = sdl.keyboard().mod_state();
+            input.end_frame(&event_pump.mouse_state(), keymod, &layout);
+            let (mouse_x, mouse_y) = input.mouse;
+            // keys that are held down do nothing while typing a search
+            let typing = palette.searching;
+            let held = |command: Command| !typing && controls.held(command, &keyboard_state, keymod);
+
+            if input.wheel != 0 {
+                // scrolling over the inspector edits the pinned cell, shift for steps of 10,
+                // elsewhere the wheel does whatever it is bound to with the held modifiers
+                let y = input.wheel.signum();
+                let step = if input.shift.down { 10 } else { 1 };
+                let edited = inspector.scroll(&mut engine_.world, &mut engine_.history, (mouse_x, mouse_y), y * step);
+                if edited {
+                    // the wheel was used by the inspector
+                } else if layout.palette.contains_point((mouse_x, mouse_y)) {
+                    palette.scroll(-y);
+                } else {
+                    match controls.wheel(keymod) {
+                        Some(Command::Zoom) => zoom_delta += y,
+                        Some(Command::BrushSize) => brush.resize(y),
+                        _ => {}
+                    }
+                }
+            }
+
+            let world_width = engine_.world.width();
+            let world_height = engine_.world.height();
+
+            if zoom_delta != 0 {
+                // zoom around the mouse when it is over the world, otherwise around the centre
+                let (zx, zy) = if camera.contains_screen(mouse_x, mouse_y) {
+                    (mouse_x, mouse_y)
+                } else {
+                    (camera.view_width / 2, camera.view_height / 2)
+                };
+                camera.zoom_at(zx, zy, zoom_delta, world_width, world_height);
+            }
+
+            // pan with the keyboard
+            let mut pan_x = 0;
+            let mut pan_y = 0;
+            if held(Command::PanLeft) {
+                pan_x -= PAN_SPEED;
+            }
+            if held(Command::PanRight) {
+                pan_x += PAN_SPEED;
+            }
+            if held(Command::PanUp) {
+                pan_y -= PAN_SPEED;
+            }
+            if held(Command::PanDown) {
+                pan_y += PAN_SPEED;
+            }
+            if pan_x != 0 || pan_y != 0 {
+                // move at least a cell per frame when zoomed in
+                camera.pan(
+                    pan_x.signum() * pan_x.abs().max(camera.zoom),
+                    pan_y.signum() * pan_y.abs().max(camera.zoom),
+                    world_width,
+                    world_height,
+                );
+            }
+
+            let in_view = camera.contains_screen(mouse_x, mouse_y);
+
+            // pan by dragging with the middle mouse button, the grabbed cell stays under the cursor.
+            // the drag starts once the mouse moves, so the same button can still click to pick
+            let was_dragging = drag_anchor.is_some();
+            if input.down(drag_button) {
+                match drag_anchor {
+                    Some((ax, ay)) => camera.anchor(ax, ay, mouse_x, mouse_y, world_width, world_height),
+                    None => {
+                        if let Some((ox, oy)) = input.drag_origin(drag_button).filter(|(ox, oy)| camera.contains_screen(*ox, *oy)) {
+                            drag_anchor = Some(camera.screen_to_world(ox, oy));
+                        }
+                    }
+                }
             } else {
-                ctrl_pressed = false;
+                drag_anchor = None;
+            }
+            let (world_x, world_y) = camera.screen_to_world(mouse_x, mouse_y);
+            if input.clicked(pick_button) && in_view && !was_dragging {
+                palette.selected = engine_.world.get(world_x as usize, world_y as usize).species;
             }
 
             canvas.set_draw_color(BACKGROUND_COLOR);
             canvas.clear();
 
             // paint the world 
-            draw_world(&mut canvas, vwidth, vheight,&engine_.world);
-
-            let mouse_state = event_pump.mouse_state();
-            let mouse_x = mouse_state.x() /2;
-            let mouse_y = mouse_state.y()/2;
+            draw_world(&mut canvas, &camera, &engine_.world);
             
-            if mouse_state.left() {
-
-                if is_in_window(mouse_x, mouse_y, (WIDTH) as i32, (HEIGHT) as i32) {
-                    let size = cursor_size;
-                    let radius: f64 = (size as f64) / 2.0;
-            
-                    let floor = (radius + 1.0) as i32;
-                    let ciel = (radius + 1.5) as i32;
-            
-                    // draw the elements as a circle
-                    for dx in -floor..ciel {
-                        for dy in -floor..ciel {
-                            if (((dx * dx) + (dy * dy)) as f64) > (radius * radius) {
-                                continue;
-                            };
-                            let px = mouse_x + dx;
-                            let py = mouse_y + dy;
-                            let i = engine_.world.get_index(px, py);
-            
-                            if px < 0 || px > (WIDTH - UI_X) as i32 - 1 || py < 0 || py > (HEIGHT-UI_Y) as i32 - 1 {
-                                continue;
-                            }
-                            if engine_.world.get(px as usize, py as usize).species == Species::EMPT || cell_species[selected_index] == Species::EMPT {
-                                engine_.world.cells[i] = Cell {
-                                    species: cell_species[selected_index],
-                                    ra: 60
-                                        + (size as u8)
-                                        + (engine_.world.rng.gen::<f32>() * 30.) as u8
-                                        + ((engine_.world.generation % 127) as i8 - 60).abs() as u8,
-                                    rb: 1,
-                                    clock: engine_.world.generation,
-   
-                                }
-                            }
-                        }
-                    }
-                    
+            // strokes only start inside the viewport but carry on when dragged out of it
+            // the right button erases with the same tool
+            let over_inspector = inspector.contains((mouse_x, mouse_y));
+            let paint_down = input.down(paint_button);
+            let erase_down = input.down(erase_button);
+            let left_pressed = input.pressed(paint_button) && in_view && !over_inspector;
+            let right_pressed = input.pressed(erase_button) && in_view && !over_inspector;
+
+            // ctrl + click pins a cell in the inspector instead of painting while paused
+            let pinning = left_pressed && inspector.open && paused && input.ctrl.down;
+            if pinning {
+                inspector.pin(world_x, world_y);
+            }
+            let stroke = if left_pressed && !pinning {
+                Some(palette.selected)
+            } else if right_pressed {
+                Some(Species::EMPT)
+            } else {
+                None
+            };
+            brush.update(
+                &mut engine_.world,
+                &mut engine_.history,
+                (world_x, world_y),
+                stroke,
+                paint_down || erase_down,
+                input.shift.down,
+            );
+            if in_view || paint_down || erase_down {
+                brush.draw_cursor(&mut canvas, &camera, &engine_.world, (world_x, world_y), palette.selected);
+            }
+            let cell = engine_.world.get(world_x as usize, world_y as usize);
 
+            let mut ui = Ui::new(&mut canvas, &font, &input);
+            // draw selected cell at the end of the screen
+            Label::new(layout.sidebar.x(), layout.sidebar.y(), format!("{:?}", palette.selected).as_str()).show(&mut ui);
+            (brush.tool, brush.mode) = draw_tools(&mut ui, layout.sidebar, brush.tool, brush.mode);
+            let toolbar_top = layout.sidebar.y() + TOOLBAR_TOP;
+            let action = toolbar(&mut ui, layout.sidebar.x(), toolbar_top, layout.sidebar.width(), paused, clock.speed_index());
+            match action {
+                Some(Action::Pause(on)) => {
+                    paused = on;
+                    inspector.unpin();
                 }
-            }
-            canvas.set_draw_color(Color::RGB(255, 255, 255));
-            // draw the cursor
-           // draw the cursor as a  thin outlinecirle
-            for dx in -cursor_size..cursor_size {
-                for dy in -cursor_size..cursor_size {
-                    if (((dx * dx) + (dy * dy)) as f64) > ((cursor_size * cursor_size) as f64) {
-                        continue;
-                    };
-                    let px = mouse_x + dx;
-                    let py = mouse_y + dy;
-
-                    if px < 0 || px > (WIDTH - UI_X) as i32 - 1 || py < 0 || py > (HEIGHT-UI_Y) as i32 - 1 {
-                        continue;
-                    }
-                    if engine_.world.get(px as usize, py as usize).species == Species::EMPT || cell_species[selected_index] == Species::EMPT {
-                        canvas.draw_point(Point::new(px as i32, py as i32)).unwrap();
+                Some(Action::Step) => {
+                    paused = true;
+                    step = true;
+                }
+                Some(Action::Clear) => engine_.history.clear(&mut engine_.world),
+                Some(Action::Save) => {
+                    if let Err(e) = engine_.world.save(Path::new(defaults::SAVE_PATH)) {
+                        eprintln!("{}", e);
                     }
                 }
+                Some(Action::Load) => match World::load(Path::new(defaults::SAVE_PATH)) {
+                    // undo and rewind history belong to the old world
+                    Ok(world) => {
+                        engine_ = Engine::with_world(world);
+                        inspector.unpin();
+                        camera.clamp(engine_.world.width(), engine_.world.height());
+                    }
+                    Err(e) => eprintln!("{}", e),
+                },
+                Some(Action::Speed(index)) => clock.set_speed_index(index),
+                None => {}
             }
-            let cell = engine_.world.get(mouse_x as usize, mouse_y as usize);
-
-            // draw selected cell at the end of the screen
-            draw_text(&mut canvas, &font, format!("{:?}", cell_species[selected_index]).as_str(), vwidth as i32 - UI_X as i32, 0);
             
 
-            // draw a zoomed in view of the nxn grid around the mouse at bottom left of the screen
-            // check if z pressed 
-            if keyboard_state.is_scancode_pressed(sdl2::keyboard::Scancode::Z) {
-                let zoomed_view = Rect::new(
-                    mouse_x - ZOOM as i32 / 2,
-                    mouse_y - ZOOM as i32 / 2,
-                    ZOOM as u32,
-                    ZOOM as u32,
-                );
-                let zoomed_view = zoomed_view
-                    .intersection(Rect::new(0, 0, WIDTH as u32, HEIGHT as u32))
-                    .unwrap();
-    
-    
-                // actual zoom logic goes here
-                let mut zoomed_pixels = Vec::new();
-                for y in zoomed_view.y()..zoomed_view.y() + zoomed_view.height() as i32 {
-                    for x in zoomed_view.x()..zoomed_view.x() + zoomed_view.width()  as i32{
-                        let cell = engine_.world.get(x as usize, y as usize);
-                        let color = cell_to_color(cell);
-                        zoomed_pixels.push(color);
-                    }
-                }
-                // convert zoomed_pixels to u8
-                let mut zoomed_pixels_u8 = Vec::new();
-                for pixel in zoomed_pixels {
-                    zoomed_pixels_u8.push(pixel.r);
-                    zoomed_pixels_u8.push(pixel.g);
-                    zoomed_pixels_u8.push(pixel.b);
-                }
-    
-                zoomed_texture
-                    .update(None, &zoomed_pixels_u8, ZOOM as usize * 3)
-                    .unwrap();
-                    
-                // draw outline of around zoomed view
-                canvas.set_draw_color(Color::RGB(255, 255, 255));
-                canvas
-                    .draw_rect(Rect::new(
-                        mouse_x - ZOOM as i32 / 2,
-                        mouse_y - ZOOM as i32 / 2,
-                        ZOOM as u32,
-                        ZOOM as u32,
-                    ))
-                    .expect("Failed to draw zoomed view");
-    
-                // draw outline around the zoom rect in the bottom left
-                canvas.set_draw_color(Color::RGB(255, 255, 255));
-                canvas
-                    .draw_rect(Rect::new(
-                        1,
-                        vheight as i32 - ZOOM as i32 -1,
-                        ZOOM as u32,
-                        ZOOM as u32,
-                    ))
-                    .expect("Failed to draw zoomed view");
-                canvas
-                    .copy(
-                        &zoomed_texture,
-                        None,
-                        Rect::new(
-                            0,
-                            vheight as i32 - ZOOM as i32,
-                            ZOOM as u32,
-                            ZOOM as u32,
-                        ),
-                    )
-                    .unwrap();
+            // show a minimap of the whole world while z is held
+            if held(Command::Minimap) {
+                draw_minimap(&mut canvas, &mut zoomed_texture, &camera, &engine_.world, layout.height);
             }
             // end of zoom logic
                 
-            if !paused {
-                engine_.world.tick();
+            // hold r to rewind, jumping back one snapshot per frame
+            let mut ticks = 0;
+            if held(Command::Rewind) {
+                engine_.rewind();
+                // keep the clock going so letting go does not run a burst of ticks
+                clock.ticks_due(true);
+                draw_text(&mut canvas, &font, "<< REWIND", layout.view.width() as i32 / 2 - 20, 0);
+            } else {
+                ticks = clock.ticks_due(paused) + step as u32;
+                for _ in 0..ticks {
+                    engine_.timeline.tick(&mut engine_.world);
+                }
             }
+            stats.record(&engine_.world, ticks);
             
            
-            selected_index = draw_scrollbar(&mut canvas, &font, 0, vheight as i32 -UI_Y as i32, vwidth-UI_X as u32, UI_Y as u32, selected_index, cell_species, (mouse_x, mouse_y), mouse_left_clicked);
+            // right click or alt + click picks the species the brush is restricted to instead
+            match palette.update(&mut canvas, &font, layout.palette, &input) {
+                Some((species, MouseButton::Left)) if !input.alt.down => palette.selected = species,
+                Some((species, _)) => brush.mode = Mode::Target(species),
+                None => {}
+            }
 
-            let end_time = std::time::Instant::now();
-            let fps_text = format!("{:?}, Temp: {} C, FPS: {:.2}",cell.get_species(), cell.ra, 1.0 / end_time.duration_since(start_time).as_secs_f32());
+            // rates are averaged over the last second, see the stats panel for more
+            let fps_text = format!("{:?} ({}, {}), FPS: {}, TPS: {}, Tick: {}, Speed: {}x",cell.get_species(), world_x, world_y, stats.fps(), stats.tps(), engine_.timeline.tick_count(), clock.speed());
             draw_text(&mut canvas, &font, &fps_text.as_str(), (0) as i32,  (0) as i32);
+            inspector.draw(&mut canvas, &font, &engine_.world, (world_x, world_y), (mouse_x, mouse_y));
+            stats.draw(&mut canvas, &font, &engine_.world, layout.view.right(), 10, layout.view.bottom());
+            if help_open {
+                let mut ui = Ui::new(&mut canvas, &font, &input);
+                controls.draw_help(&mut ui, layout.view);
+            }
             canvas.present();
         }
     }
 }
 
-// SEGFAULTS SOMETIMES SO WE WILL NOT USE THIS FUNCTION FOR NOW UNTIL WE FIGURE OUT WHY
-fn draw_zoom(canvas: &mut Canvas<sdl2::video::Window>, zoomed_texture: &mut Texture, mouse_x: u32, mouse_y:u32, world: &World, vwidth: u32, vheight: u32) {
-
-    let zoomed_view = Rect::new(
-        mouse_x as i32 - ZOOM as i32 / 2,
-        mouse_y as i32 - ZOOM  as i32 / 2,
-        ZOOM as u32,
-        ZOOM as u32,
-    );
-
-    let zoomed_view = zoomed_view
-                .intersection(Rect::new(0, 0, WIDTH as u32, HEIGHT as u32))
-                .unwrap();
-
-    let mut zoomed_pixels =  Vec::new();
-    for y in zoomed_view.y()..zoomed_view.y() + zoomed_view.height() as i32 {
-        for x in zoomed_view.x()..zoomed_view.x() + zoomed_view.width()  as i32{
-            let cell = world.get(x as usize, y as usize);
+// draw the whole world scaled into a ZOOM x ZOOM box in the bottom left, with the camera view outlined
+fn draw_minimap(canvas: &mut Canvas<sdl2::video::Window>, texture: &mut Texture, camera: &Camera, world: &World, bottom: i32) {
+    // keep the aspect ratio of the world, the longest side spans the whole box
+    let longest = world.width().max(world.height()).max(1);
+    let map_width = (world.width() * ZOOM / longest).max(1);
+    let map_height = (world.height() * ZOOM / longest).max(1);
+
+    let mut pixels = vec![0u8; (ZOOM * ZOOM * 3) as usize];
+    for ty in 0..map_height {
+        for tx in 0..map_width {
+            let cell = world.get((tx * longest / ZOOM) as usize, (ty * longest / ZOOM) as usize);
             let color = cell_to_color(cell);
-            
-            zoomed_pixels.push(color);
-        }
-        let mut zoomed_pixels_u8 = Vec::new();
-        for pixel in zoomed_pixels.clone() {
-            zoomed_pixels_u8.push(pixel.r);
-            zoomed_pixels_u8.push(pixel.g);
-            zoomed_pixels_u8.push(pixel.b);
+            let i = ((tx + ty * ZOOM) * 3) as usize;
+            pixels[i] = color.r;
+            pixels[i + 1] = color.g;
+            pixels[i + 2] = color.b;
         }
+    }
+    texture
+        .update(None, &pixels, ZOOM as usize * 3)
+        .unwrap();
 
-        zoomed_texture
-        .update(None, &zoomed_pixels_u8, ZOOM as usize * 3)
+    let top = bottom - map_height;
+    canvas
+        .copy(
+            texture,
+            Rect::new(0, 0, map_width as u32, map_height as u32),
+            Rect::new(0, top, map_width as u32, map_height as u32),
+        )
         .unwrap();
-        canvas.set_draw_color(Color::RGB(255, 255, 255));
-            canvas
-                .draw_rect(Rect::new(
-                    mouse_x as i32 - ZOOM as i32 / 2,
-                    mouse_y as i32 - ZOOM as i32 / 2,
-                    ZOOM as u32,
-                    ZOOM as u32,
-                ))
-                .expect("Failed to draw zoomed view");
-
-            // draw outline around the zoom rect in the bottom left
-            canvas.set_draw_color(Color::RGB(255, 255, 255));
-            canvas
-                .draw_rect(Rect::new(
-                    1,
-                    vheight as i32 - ZOOM as i32 -1,
-                    ZOOM as u32,
-                    ZOOM as u32,
-                ))
-                .expect("Failed to draw zoomed view");
-                
-            canvas
-                .copy(
-                    &zoomed_texture,
-                    None,
-                    Rect::new(
-                        0,
-                        vheight as i32 - ZOOM as i32,
-                        ZOOM as u32,
-                        ZOOM as u32,
-                    ),
-                )
-                .unwrap();
-            }
-            
-    
+
+    canvas.set_draw_color(Color::RGB(255, 255, 255));
+    canvas
+        .draw_rect(Rect::new(0, top, map_width as u32, map_height as u32))
+        .expect("Failed to draw minimap");
+
+    // outline the part of the world the camera is looking at
+    let (cells_x, cells_y) = camera.visible_cells();
+    canvas.set_draw_color(Color::RGB(255, 0, 0));
+    canvas
+        .draw_rect(Rect::new(
+            camera.x * ZOOM / longest,
+            top + camera.y * ZOOM / longest,
+            (cells_x.min(world.width()) * ZOOM / longest).max(1) as u32,
+            (cells_y.min(world.height()) * ZOOM / longest).max(1) as u32,
+        ))
+        .expect("Failed to draw minimap view");
 }
 
-fn draw_world(canvas: &mut Canvas<sdl2::video::Window>, width_: u32, height_:u32, world: &World) {
-    // paint the world 
-    for y in (0..height_ - UI_Y as u32).rev() {
-        for x in 0..width_-UI_X as u32 {
+fn draw_world(canvas: &mut Canvas<sdl2::video::Window>, camera: &Camera, world: &World) {
+    // paint the world, cells at the edges may only be partially visible
+    canvas.set_clip_rect(Rect::new(0, 0, camera.view_width as u32, camera.view_height as u32));
+    let (cells_x, cells_y) = camera.visible_cells();
+    for y in (camera.y..camera.y + cells_y).rev() {
+        for x in camera.x..camera.x + cells_x {
+            if x >= world.width() || y >= world.height() {
+                continue;
+            }
             let cell = world.get(x as usize, y as usize);
+            if cell.species == Species::EMPT {
+                continue;
+            }
             let color = cell_to_color(cell);
             canvas.set_draw_color(color);
-            // draw a pixel using rect
+            let (sx, sy) = camera.world_to_screen(x, y);
+            // draw a cell using rect
             canvas
-                .fill_rect(Rect::new(x as i32, y as i32, 1, 1))
+                .fill_rect(Rect::new(sx, sy, camera.zoom as u32, camera.zoom as u32))
                 .expect("Failed to draw pixel");
         }
     }
+    canvas.set_clip_rect(None);
 }
 
-fn draw_text(canvas: &mut Canvas<sdl2::video::Window>, font: &sdl2::ttf::Font, text: &str, x: i32, y: i32) {
-    let surface = font.render(text).blended(Color::RGB(150, 150, 150)).unwrap();
-    // use let binding to avoid borrowing issues
-    let texture_creator = canvas.texture_creator();
-    let texture = texture_creator.create_texture_from_surface(&surface).unwrap();
-    let texture_query = texture.query();
-    canvas
-        .copy(
-            &texture,
-            None,
-            Rect::new(x, y, texture_query.width, texture_query.height),
-        )
-        .expect("Failed to copy texture");
+fn draw_text(canvas: &mut Canvas<sdl2::video::Window>, font: &Glyphs, text: &str, x: i32, y: i32) {
+    font.draw(canvas, text, x, y, TEXT_COLOR);
 }
 
-//implement horizontal scrollbar which displays clickable list of species
-fn draw_scrollbar(canvas: &mut Canvas<sdl2::video::Window>, font: &sdl2::ttf::Font, x: i32, y: i32, width: u32, height: u32,mut selected_index: usize, species: &[Species], mouse_coords : (i32, i32), clicked: bool) -> usize {
-    // draw scrollbar
-    canvas.set_draw_color(Color::RGB(0, 0, 0));
-    canvas
-        .fill_rect(Rect::new(x, y, width, height))
-        .expect("Failed to draw scrollbar");
-    // draw scrollbar slider
-    canvas.set_draw_color(Color::RGB(255, 255, 255));
-    canvas
-        .fill_rect(Rect::new(
-            x + 1 + (selected_index as i32) * width as i32 / species.len() as i32,
-            y + 1,
-            width / species.len() as u32 - 2,
-            height -1,
-        ))
-        .expect("Failed to draw scrollbar slider");
-    // draw scrollbar text
-    for (i, species) in species.iter().enumerate() {
-        draw_text(
-            canvas,
-            font,
-            format!("{:?}", species).as_str(),
-            x as i32 + (i as u32 * width / SPECIES_COUNT as u32 ) as i32 + 5,
-            y as i32 + 5,
-        );
-    }
 
-    // if the mouse is hovering over a species, draw some outline around it
-    if mouse_coords.0 > x && mouse_coords.0 < x + width as i32 && mouse_coords.1 > y && mouse_coords.1 < y + height as i32 {
-        let index = ((mouse_coords.0 - x) as u32 * SPECIES_COUNT as u32 / width) as usize;
-        canvas.set_draw_color(Color::RGB(255, 0, 0));
-        canvas
-            .draw_rect(Rect::new(
-                x + 1 + (index as i32) * width as i32 / species.len() as i32,
-                y + 1,
-                width / species.len() as u32 - 2,
-                height -1,
-            ))
-            .expect("Failed to draw scrollbar slider");
-        if clicked {
-            selected_index = index;
+// list the brush tools down the side bar followed by the placement mode,
+// clicking a tool selects it and clicking the mode cycles through the modes
+fn draw_tools(ui: &mut Ui, area: Rect, mut selected: Tool, mut mode: Mode) -> (Tool, Mode) {
+    let slot_at = |i: usize| Rect::new(area.x(), area.y() + TOOL_SLOT_TOP + i as i32 * TOOL_SLOT_HEIGHT, area.width(), TOOL_SLOT_HEIGHT as u32);
+
+    for (i, tool) in Tool::all().into_iter().enumerate() {
+        if Toggle::new(slot_at(i), tool.label(), tool == selected).show(ui).is_some() {
+            selected = tool;
         }
     }
-    // check if mouse is hovering over scrollbar
-    if mouse_coords.0 > x && mouse_coords.0 < x + width as i32 && mouse_coords.1 > y && mouse_coords.1 < y + height as i32 {
-        // check if mouse is clicked
-        if clicked {
-            // get index at where we clicked
-            selected_index = (mouse_coords.0 - x) as usize * SPECIES_COUNT / width as usize;
-            // set selected_index to the index of the species that the mouse is hovering over
-        }
-        
+
+    // leave a gap between the tools and the mode
+    let label = mode.label();
+    if Button::new(slot_at(Tool::all().len() + 1), label.as_str()).show(ui) {
+        mode = mode.next();
     }
-    return selected_index
+
+    (selected, mode)
 }
@@ -0,0 +1,200 @@
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::engine::species::{Category, Species};
+
+use super::components::glyphs::Glyphs;
+use super::draw_text;
+use super::input::InputState;
+use super::utils::species_color;
+
+const TAB_WIDTH: i32 = 28;
+const TAB_HEIGHT: i32 = 12;
+const SLOT_WIDTH: i32 = 36;
+const SWATCH_SIZE: u32 = 6;
+const MAX_SEARCH_LENGTH: usize = 12;
+
+fn category_label(category: Option<Category>) -> &'static str {
+    match category {
+        None => "ALL",
+        Some(Category::Powder) => "POWD",
+        Some(Category::Liquid) => "LIQD",
+        Some(Category::Gas) => "GAS",
+        Some(Category::Solid) => "SOLD",
+        Some(Category::Life) => "LIFE",
+        Some(Category::Special) => "SPEC",
+    }
+}
+
+// species picker along the bottom of the screen. a row of category tabs and a
+// search box sit above the species matching them, which scroll sideways when
+// they do not all fit
+pub struct Palette {
+    pub selected: Species,
+    category: Option<Category>,
+    search: String,
+    pub searching: bool,
+    scroll: usize,
+}
+
+impl Palette {
+    pub fn new() -> Palette {
+        Palette {
+            selected: Species::DUST,
+            category: None,
+            search: String::new(),
+            searching: false,
+            scroll: 0,
+        }
+    }
+
+    // species in the current category whose name or description matches the search
+    fn visible(&self) -> Vec<Species> {
+        let search = self.search.to_lowercase();
+        Species::all()
+            .into_iter()
+            .filter(|species| self.category.is_none_or(|category| species.category() == category))
+            .filter(|species| {
+                format!("{:?}", species).to_lowercase().contains(&search)
+                    || species.description().to_lowercase().contains(&search)
+            })
+            .collect()
+    }
+
+    // number keys pick the nth species shown, counting from the first visible slot
+    pub fn hotkey(&mut self, n: usize) {
+        if let Some(species) = self.visible().into_iter().skip(self.scroll).nth(n) {
+            self.selected = species;
+        }
+    }
+
+    pub fn scroll(&mut self, delta: i32) {
+        let max = self.visible().len().saturating_sub(1);
+        self.scroll = (self.scroll as i32 + delta).clamp(0, max as i32) as usize;
+    }
+
+    pub fn start_search(&mut self) {
+        self.searching = true;
+    }
+
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            // the slash that opened the search arrives as text too
+            if self.search.len() < MAX_SEARCH_LENGTH && !c.is_control() && c != '/' {
+                self.search.push(c);
+            }
+        }
+        self.scroll = 0;
+    }
+
+    // keys while the search box has focus, enter keeps the search and escape drops it
+    pub fn search_key(&mut self, keycode: Keycode) {
+        match keycode {
+            Keycode::Backspace => {
+                self.search.pop();
+                self.scroll = 0;
+            }
+            Keycode::Return | Keycode::KpEnter => self.searching = false,
+            Keycode::Escape => {
+                self.search.clear();
+                self.searching = false;
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+    }
+
+    // draw the palette into `area` and handle clicks on it, returns the species
+    // clicked this frame and the button used so the caller can decide what to do with it
+    pub fn update(&mut self, canvas: &mut Canvas<Window>, font: &Glyphs, area: Rect, input: &InputState) -> Option<(Species, MouseButton)> {
+        let mouse_coords = input.mouse;
+        let clicked = input.pressed(MouseButton::Left);
+        canvas.set_draw_color(Color::RGB(0, 0, 0));
+        canvas.fill_rect(area).expect("Failed to draw palette");
+
+        // category tabs
+        let categories = std::iter::once(None).chain(Category::all().into_iter().map(Some));
+        let mut tabs_end = area.x();
+        for (i, category) in categories.enumerate() {
+            let tab = Rect::new(area.x() + i as i32 * TAB_WIDTH, area.y(), TAB_WIDTH as u32, TAB_HEIGHT as u32);
+            tabs_end = tab.right();
+            if tab.contains_point(mouse_coords) {
+                if clicked {
+                    self.category = category;
+                    self.scroll = 0;
+                }
+                canvas.set_draw_color(Color::RGB(255, 0, 0));
+                canvas.draw_rect(tab).expect("Failed to draw palette tab");
+            } else if category == self.category {
+                canvas.set_draw_color(Color::RGB(255, 255, 255));
+                canvas.draw_rect(tab).expect("Failed to draw palette tab");
+            }
+            draw_text(canvas, font, category_label(category), tab.x() + 3, tab.y() + 1);
+        }
+
+        // search box
+        let search_box = Rect::new(tabs_end + 4, area.y(), (area.right() - tabs_end - 6).max(1) as u32, TAB_HEIGHT as u32);
+        if clicked {
+            self.searching = search_box.contains_point(mouse_coords);
+        }
+        canvas.set_draw_color(if self.searching { Color::RGB(255, 255, 255) } else { Color::RGB(100, 100, 100) });
+        canvas.draw_rect(search_box).expect("Failed to draw search box");
+        let search_text = if self.searching {
+            format!("{}_", self.search)
+        } else if self.search.is_empty() {
+            "/ search".to_string()
+        } else {
+            self.search.clone()
+        };
+        draw_text(canvas, font, search_text.as_str(), search_box.x() + 3, search_box.y() + 1);
+
+        // species slots
+        let row = Rect::new(area.x(), area.y() + TAB_HEIGHT + 1, area.width(), (area.height() as i32 - TAB_HEIGHT - 1).max(1) as u32);
+        let slots = (row.width() as i32 / SLOT_WIDTH).max(1) as usize;
+        let mut picked = None;
+        let mut hovered = None;
+        for (i, species) in self.visible().into_iter().skip(self.scroll).take(slots).enumerate() {
+            let slot = Rect::new(row.x() + i as i32 * SLOT_WIDTH, row.y(), SLOT_WIDTH as u32, row.height());
+            if species == self.selected {
+                canvas.set_draw_color(Color::RGB(255, 255, 255));
+                canvas.draw_rect(slot).expect("Failed to draw palette slot");
+            }
+            if slot.contains_point(mouse_coords) {
+                canvas.set_draw_color(Color::RGB(255, 0, 0));
+                canvas.draw_rect(slot).expect("Failed to draw palette slot");
+                hovered = Some(species);
+                if clicked {
+                    picked = Some((species, MouseButton::Left));
+                } else if input.pressed(MouseButton::Right) {
+                    picked = Some((species, MouseButton::Right));
+                }
+            }
+
+            let swatch_y = slot.y() + (slot.height() - SWATCH_SIZE) as i32 / 2;
+            canvas.set_draw_color(species_color(species));
+            canvas
+                .fill_rect(Rect::new(slot.x() + 3, swatch_y, SWATCH_SIZE, SWATCH_SIZE))
+                .expect("Failed to draw swatch");
+            draw_text(canvas, font, format!("{:?}", species).as_str(), slot.x() + 5 + SWATCH_SIZE as i32, slot.y() + 4);
+        }
+
+        // describe the hovered species just above the palette
+        if let Some(species) = hovered {
+            let text = species.description();
+            let (width, height) = (font.width_of(text), font.height());
+            let x = mouse_coords.0.min(area.right() - width as i32 - 6).max(area.x());
+            let tooltip = Rect::new(x, area.y() - height as i32 - 4, width + 6, height + 4);
+            canvas.set_draw_color(Color::RGB(20, 20, 20));
+            canvas.fill_rect(tooltip).expect("Failed to draw tooltip");
+            canvas.set_draw_color(Color::RGB(255, 255, 255));
+            canvas.draw_rect(tooltip).expect("Failed to draw tooltip");
+            draw_text(canvas, font, text, tooltip.x() + 3, tooltip.y() + 2);
+        }
+
+        picked
+    }
+}
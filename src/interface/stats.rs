@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::engine::species::{Species, SPECIES_COUNT};
+use crate::engine::World;
+
+use super::components::glyphs::Glyphs;
+use super::draw_text;
+use super::utils::species_color;
+
+// rates are averaged over this window
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+// species counts are sampled this often for the graphs
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+const SAMPLES: usize = 60;
+
+const LINE_HEIGHT: i32 = 9;
+const PANEL_WIDTH: i32 = 140;
+const GRAPH_WIDTH: i32 = SAMPLES as i32;
+
+// frame and tick rates plus the population of every species over time
+pub struct Stats {
+    pub open: bool,
+    frames: VecDeque<Instant>,
+    ticks: VecDeque<(Instant, u32)>,
+    samples: VecDeque<[usize; SPECIES_COUNT]>,
+    last_sample: Option<Instant>,
+}
+
+impl Stats {
+    pub fn new() -> Stats {
+        Stats {
+            open: false,
+            frames: VecDeque::new(),
+            ticks: VecDeque::new(),
+            samples: VecDeque::new(),
+            last_sample: None,
+        }
+    }
+
+    // call once per rendered frame with the number of ticks run during it
+    pub fn record(&mut self, world: &World, ticks: u32) {
+        let now = Instant::now();
+        self.frames.push_back(now);
+        self.ticks.push_back((now, ticks));
+        while self.frames.front().is_some_and(|t| now.duration_since(*t) > RATE_WINDOW) {
+            self.frames.pop_front();
+        }
+        while self.ticks.front().is_some_and(|(t, _)| now.duration_since(*t) > RATE_WINDOW) {
+            self.ticks.pop_front();
+        }
+
+        // counting walks the whole world, so only do it while the panel is shown
+        let due = self.last_sample.is_none_or(|t| now.duration_since(t) >= SAMPLE_INTERVAL);
+        if self.open && due {
+            self.samples.push_back(world.species_counts());
+            if self.samples.len() > SAMPLES {
+                self.samples.pop_front();
+            }
+            self.last_sample = Some(now);
+        }
+    }
+
+    pub fn fps(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn tps(&self) -> u32 {
+        self.ticks.iter().map(|(_, ticks)| ticks).sum()
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.samples.clear();
+        self.last_sample = None;
+    }
+
+    // draw the panel with its top right corner at (right, top), species that do
+    // not fit above `bottom` go into further columns to the left
+    pub fn draw(&self, canvas: &mut Canvas<Window>, font: &Glyphs, world: &World, right: i32, top: i32, bottom: i32) {
+        if !self.open {
+            return;
+        }
+        let species = &Species::all()[1..];
+        let rows = ((bottom - top - 4) / LINE_HEIGHT - 2).clamp(1, species.len() as i32);
+        let columns = (species.len() as i32 + rows - 1) / rows;
+        let width = PANEL_WIDTH * columns;
+        let area = Rect::new(right - width, top, width as u32, ((rows + 2) * LINE_HEIGHT + 4) as u32);
+        canvas.set_draw_color(Color::RGB(20, 20, 20));
+        canvas.fill_rect(area).expect("Failed to draw stats");
+        canvas.set_draw_color(Color::RGB(255, 255, 255));
+        canvas.draw_rect(area).expect("Failed to draw stats");
+
+        let (active, chunks) = world.chunk_activity();
+        let x = area.x() + 3;
+        let mut y = area.y() + 2;
+        draw_text(canvas, font, format!("FPS {} TPS {}", self.fps(), self.tps()).as_str(), x, y);
+        y += LINE_HEIGHT;
+        draw_text(canvas, font, format!("chunks {}/{}", active, chunks).as_str(), x, y);
+        y += LINE_HEIGHT;
+
+        // empty cells are left out, they would dwarf everything else
+        let latest = self.samples.back();
+        for (i, species) in species.iter().enumerate() {
+            let column_x = x + i as i32 / rows * PANEL_WIDTH;
+            let row_y = y + i as i32 % rows * LINE_HEIGHT;
+            let count = latest.map_or(0, |counts| counts[*species as usize]);
+            draw_text(canvas, font, format!("{:?} {}", species, count).as_str(), column_x, row_y);
+            let graph_x = column_x + PANEL_WIDTH - GRAPH_WIDTH - 6;
+            self.draw_graph(canvas, *species, Rect::new(graph_x, row_y + 1, GRAPH_WIDTH as u32, (LINE_HEIGHT - 2) as u32));
+        }
+    }
+
+    // sparkline of the count of a species, scaled to its own maximum
+    fn draw_graph(&self, canvas: &mut Canvas<Window>, species: Species, area: Rect) {
+        let counts: Vec<usize> = self.samples.iter().map(|counts| counts[species as usize]).collect();
+        let max = counts.iter().copied().max().unwrap_or(0).max(1);
+        let points: Vec<Point> = counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let height = (*count * (area.height() as usize - 1) / max) as i32;
+                Point::new(area.x() + i as i32, area.bottom() - 1 - height)
+            })
+            .collect();
+
+        canvas.set_draw_color(species_color(species));
+        if points.len() > 1 {
+            canvas.draw_lines(points.as_slice()).expect("Failed to draw graph");
+        } else {
+            canvas.draw_points(points.as_slice()).expect("Failed to draw graph");
+        }
+    }
+}
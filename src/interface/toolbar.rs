@@ -0,0 +1,54 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+use super::clock::SPEEDS;
+use super::components::button::Button;
+use super::components::label::Label;
+use super::components::panel::Panel;
+use super::components::slider::Slider;
+use super::components::toggle::Toggle;
+use super::components::Ui;
+
+const ROW_HEIGHT: i32 = 12;
+const ROWS: i32 = 7;
+
+// what the toolbar asks the main loop to do, the loop owns the engine and
+// applies these after the toolbar is drawn
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Pause(bool),
+    Step,
+    Clear,
+    Save,
+    Load,
+    Speed(usize),
+}
+
+// simulation controls stacked in a column of `width` starting at (x, y)
+pub fn toolbar(ui: &mut Ui, x: i32, y: i32, width: u32, paused: bool, speed_index: usize) -> Option<Action> {
+    let row = |i: i32| Rect::new(x, y + i * ROW_HEIGHT, width, ROW_HEIGHT as u32);
+    Panel::new(Rect::new(x, y, width, (ROWS * ROW_HEIGHT) as u32)).show(ui);
+    Label::new(x + 2, y + 1, "SIM").color(Color::RGB(255, 255, 255)).show(ui);
+
+    let mut action = None;
+    if let Some(on) = Toggle::new(row(1), "PAUS", paused).show(ui) {
+        action = Some(Action::Pause(on));
+    }
+    if Button::new(row(2), "STEP").show(ui) {
+        action = Some(Action::Step);
+    }
+    if Button::new(row(3), "CLR").show(ui) {
+        action = Some(Action::Clear);
+    }
+    if Button::new(row(4), "SAVE").show(ui) {
+        action = Some(Action::Save);
+    }
+    if Button::new(row(5), "LOAD").show(ui) {
+        action = Some(Action::Load);
+    }
+    let speed = format!("{}x", SPEEDS[speed_index]);
+    if let Some(index) = Slider::new(row(6), speed.as_str(), speed_index as i32, 0, SPEEDS.len() as i32 - 1).show(ui) {
+        action = Some(Action::Speed(index as usize));
+    }
+    action
+}
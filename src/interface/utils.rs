@@ -1,31 +1,47 @@
 use sdl2::pixels::Color;
 
 use crate::engine::Cell;
-use crate::engine::species::Species;
+use crate::engine::species::{is_stained, Species, SMOKE_DENSITY};
 use crate::interface::vary_color;
 
 use super::BACKGROUND_COLOR;
 
+pub fn species_color(species: Species) -> Color {
+    let (r, g, b) = species.color();
+    Color::RGB(r, g, b)
+}
+
 pub fn cell_to_color(cell: Cell) -> Color {
     match cell.get_species() {
         Species::EMPT => BACKGROUND_COLOR,
-        Species::WALL => Color::RGB(255, 255, 255),
-        Species::DUST => vary_color(Color::RGB(255, 200, 230)),
-        Species::SAND => vary_color(Color::RGB(255, 200, 100)),
-        Species::WATR => vary_color(Color::RGB(100, 100, 255)),
-        Species::GAS => vary_color(Color::RGB(255, 255, 255)),
-        Species::OIL => vary_color(Color::RGB(255, 100, 0)),
-        Species::FIRE => vary_color(Color::RGB(255, 120, 0)),
-        Species::SMKE => vary_color(Color::RGB(100, 100, 100)),
+        _ if is_stained(cell) => vary_color(stained_color(cell.species)),
+        Species::WALL => species_color(Species::WALL),
         Species::GOL => match cell.rb {
             // check if cell is alive or dead when ra  is 1
-            1 => vary_color(Color::RGB(255, 255, 255)),
+            1 => vary_color(species_color(Species::GOL)),
             _ => vary_color(Color::RGB(0, 0, 0)),
         },
-        Species::WOOD => vary_color(Color::RGB(100, 50, 0)),
-        Species::OXGN => vary_color(Color::RGB(146,182,213)),
-        Species::HYGN => vary_color(Color::RGB(51, 71, 109)),
-
-        
+        // glowing charcoal
+        Species::CHARCOAL if cell.rb > 1 => vary_color(Color::RGB(220, 80, 30)),
+        Species::SMKE if cell.ra == 0 => vary_color(smoke_color(SMOKE_DENSITY)),
+        Species::SMKE => vary_color(smoke_color(cell.ra)),
+        species => vary_color(species_color(species)),
     }
-}
\ No newline at end of file
+}
+
+// thin smoke is lighter than the species colour and thick smoke darker
+fn smoke_color(density: u8) -> Color {
+    let (r, g, b) = Species::SMKE.color();
+    let factor = 1.5 - density as f32 / 255.0;
+    // vary_color takes up to 50 off every channel that is not zero
+    let shade = |channel: u8| ((channel as f32 * factor) as u8).max(50);
+    Color::RGB(shade(r), shade(g), shade(b))
+}
+
+// a third of the species colour, for cells soot has stained
+fn stained_color(species: Species) -> Color {
+    let (r, g, b) = species.color();
+    // vary_color takes up to 50 off every channel that is not zero
+    let shade = |channel: u8| (channel / 3).max(50);
+    Color::RGB(shade(r), shade(g), shade(b))
+}
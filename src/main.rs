@@ -1,9 +1,115 @@
 mod engine;
 mod interface;
 
-use engine::Engine;
-use interface::Interface;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use engine::{definitions, Engine, World, MAX_CELLS};
+use interface::{Interface, Options};
+
+const USAGE: &str = "usage: crumb [options]
+
+options:
+    --size <W>x<H>         size of a new world in cells
+    --seed <N>             seed for the simulation's random numbers
+    --load <path>          start from a world file: a saved .crumb world,
+                           a .txt ascii map or an image
+    --palette <path>       colour to species table used when loading an image
+    --species <path>       species definition file overriding colours,
+                           categories and descriptions
+    --scale <N>            size of a logical pixel, picked from the display
+                           density by default
+    --paused               start with the simulation paused
+    --max-tps <N>          never run more than N ticks per second
+    -h, --help             print this help";
+
+// print the problem with the command line and stop
+fn usage_error(message: String) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    std::process::exit(2);
+}
+
+fn value<T: FromStr>(flag: &str, value: Option<String>) -> T {
+    let Some(value) = value else {
+        usage_error(format!("{} needs a value", flag));
+    };
+    value
+        .parse()
+        .unwrap_or_else(|_| usage_error(format!("bad value for {}: {}", flag, value)))
+}
+
+// a value that has to be above zero
+fn positive<T: FromStr + PartialOrd + Default>(flag: &str, text: Option<String>) -> T {
+    let parsed: T = value(flag, text.clone());
+    // also turns away NaN, which is neither above nor below zero
+    if parsed.partial_cmp(&T::default()) != Some(Ordering::Greater) {
+        usage_error(format!("{} must be above zero, got {}", flag, text.unwrap_or_default()));
+    }
+    parsed
+}
+
+fn parse_size(flag: &str, text: String) -> (i32, i32) {
+    let size = text
+        .split_once('x')
+        .and_then(|(width, height)| Some((width.parse::<i32>().ok()?, height.parse::<i32>().ok()?)))
+        .filter(|(width, height)| *width > 0 && *height > 0);
+    let (width, height) = size.unwrap_or_else(|| usage_error(format!("bad value for {}: {}, expected e.g. 400x300", flag, text)));
+    if !matches!(width.checked_mul(height), Some(cells) if cells <= MAX_CELLS) {
+        usage_error(format!("{} {} is too large, a world can have at most {} cells", flag, text, MAX_CELLS));
+    }
+    (width, height)
+}
+
+fn or_exit<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
 fn main() {
-    let engine = Engine::new();
-    Interface::run(engine);
+    let mut size: Option<(i32, i32)> = None;
+    let mut seed: Option<u64> = None;
+    let mut load: Option<PathBuf> = None;
+    let mut palette: Option<PathBuf> = None;
+    let mut species: Option<PathBuf> = None;
+    let mut options = Options::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = Some(parse_size(&arg, value(&arg, args.next()))),
+            "--seed" => seed = Some(value(&arg, args.next())),
+            "--load" => load = Some(value(&arg, args.next())),
+            "--palette" => palette = Some(value(&arg, args.next())),
+            "--species" => species = Some(value(&arg, args.next())),
+            "--scale" => options.scale = Some(positive(&arg, args.next())),
+            "--paused" => options.paused = true,
+            "--max-tps" => options.max_tps = Some(positive(&arg, args.next())),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => usage_error(format!("unknown argument: {}", arg)),
+        }
+    }
+    if load.is_some() && size.is_some() {
+        usage_error("--size cannot be used with --load, the file decides the size".to_string());
+    }
+
+    // colours have to be known before an image is matched against them
+    if let Some(path) = species {
+        or_exit(definitions::load(&path));
+    }
+
+    let mut engine = match (load, size) {
+        (Some(path), _) => Engine::with_world(or_exit(World::open(&path, palette.as_deref()))),
+        (None, Some((width, height))) => Engine::with_world(World::new(width, height)),
+        (None, None) => Engine::new(),
+    };
+    if let Some(seed) = seed {
+        engine.world.seed(seed);
+    }
+    Interface::run(engine, options);
 }